@@ -0,0 +1,289 @@
+//! Cross-language model parity
+//!
+//! AppleSignInKit decodes the JSON the server builds from the TypeScript
+//! interfaces in the core and SvelteKit packages. This command pairs every
+//! `Codable` struct with the interfaces of the same name (or the one named
+//! in `model_map`) and reports key and optionality drift between them.
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::{error, info};
+
+use super::{generate, validate};
+use crate::extractors::{swift, typescript};
+use crate::types::{
+    DocgenConfig, Export, ExportKind, IssueSeverity, ModelComparison, ModelKey, PackageKind,
+    Property, SwiftStruct, ValidationIssue, ValidationResult,
+};
+
+/// Run the model parity check
+pub async fn run(root: &str, strict: bool) -> Result<()> {
+    let root_path = Path::new(root);
+    let config = generate::load_or_create_config(root_path)?;
+
+    info!("Checking Swift models against TypeScript interfaces");
+
+    let comparisons = collect(root_path, &config).await?;
+
+    let mut result = ValidationResult {
+        passed: true,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        info: Vec::new(),
+    };
+
+    for comparison in &comparisons {
+        for issue in &comparison.issues {
+            match issue.severity {
+                IssueSeverity::Error => result.errors.push(issue.clone()),
+                IssueSeverity::Warning => result.warnings.push(issue.clone()),
+                IssueSeverity::Info => result.info.push(issue.message.clone()),
+            }
+        }
+        result.info.push(format!(
+            "{}: compared with {}",
+            comparison.swift.name,
+            comparison
+                .typescript
+                .iter()
+                .map(|(pkg, export)| format!("{} in {}", export.name, pkg))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    if comparisons.is_empty() {
+        result
+            .info
+            .push("No Codable structs matched a TypeScript interface".to_string());
+    }
+
+    validate::report_results(&result, strict);
+
+    if !result.errors.is_empty() || (strict && !result.warnings.is_empty()) {
+        result.passed = false;
+    }
+
+    if result.passed {
+        info!("✅ Swift models match the TypeScript interfaces!");
+        Ok(())
+    } else {
+        error!("❌ Model drift detected!");
+        std::process::exit(1);
+    }
+}
+
+/// Pair every `Codable` Swift struct with its TypeScript interfaces
+pub async fn collect(root: &Path, config: &DocgenConfig) -> Result<Vec<ModelComparison>> {
+    let mut interfaces: Vec<(String, Export)> = Vec::new();
+    for pkg in &config.packages {
+        if !matches!(pkg.kind, PackageKind::Core | PackageKind::Frontend) {
+            continue;
+        }
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root.join(&pkg.path)
+        };
+        for file in typescript::source_files(&pkg_path) {
+            for export in typescript::extract_file(&file).await? {
+                if export.kind == ExportKind::Interface {
+                    interfaces.push((pkg.name.clone(), export));
+                }
+            }
+        }
+    }
+
+    let mut comparisons = Vec::new();
+    for (swift_package, pkg_path) in swift::find_packages(root)? {
+        let strategies = swift::key_strategies(&pkg_path)?;
+
+        for file in swift::source_files(&pkg_path) {
+            for model in swift::extract_structs(&file)? {
+                if !is_codable(&model) {
+                    continue;
+                }
+                let ts_name = config
+                    .model_map
+                    .iter()
+                    .find(|m| m.swift == model.name)
+                    .map_or(model.name.as_str(), |m| m.typescript.as_str());
+
+                let typescript: Vec<_> = interfaces
+                    .iter()
+                    .filter(|(_, export)| export.name == ts_name)
+                    .cloned()
+                    .collect();
+                if typescript.is_empty() {
+                    continue;
+                }
+
+                comparisons.push(compare(&swift_package, model, typescript, strategies));
+            }
+        }
+    }
+
+    Ok(comparisons)
+}
+
+fn is_codable(model: &SwiftStruct) -> bool {
+    model
+        .conformances
+        .iter()
+        .any(|c| c == "Codable" || c == "Decodable" || c == "Encodable")
+}
+
+/// Line up both sides by JSON key and record the drift
+fn compare(
+    swift_package: &str,
+    model: SwiftStruct,
+    typescript: Vec<(String, Export)>,
+    strategies: swift::KeyStrategies,
+) -> ModelComparison {
+    let decodes = model
+        .conformances
+        .iter()
+        .any(|c| c == "Codable" || c == "Decodable");
+
+    // Swift key -> key on the wire, following the coder's key strategy
+    let wire_key = |key: &str| {
+        if !decodes && strategies.encode_snake_case {
+            swift::to_snake_case(key)
+        } else {
+            key.to_string()
+        }
+    };
+    let matches = |ts_name: &str, swift_key: &str| {
+        if decodes && strategies.decode_snake_case {
+            swift::from_snake_case(ts_name) == swift_key
+        } else {
+            ts_name == wire_key(swift_key)
+        }
+    };
+    let ts_member = |export: &Export, swift_key: &str| -> Option<Property> {
+        export
+            .properties
+            .iter()
+            .find(|m| !m.method && matches(&m.name, swift_key))
+            .cloned()
+    };
+
+    let mut keys: Vec<ModelKey> = Vec::new();
+    for (property, key) in swift::coded_properties(&model) {
+        let members: Vec<_> = typescript
+            .iter()
+            .map(|(_, export)| ts_member(export, &key))
+            .collect();
+        let wire = members
+            .iter()
+            .flatten()
+            .next()
+            .map_or_else(|| wire_key(&key), |m| m.name.clone());
+        keys.push(ModelKey {
+            key: wire,
+            swift: Some(property.clone()),
+            typescript: members,
+        });
+    }
+
+    // Keys only the TypeScript side declares
+    for (index, (_, export)) in typescript.iter().enumerate() {
+        for member in export.properties.iter().filter(|m| !m.method) {
+            if let Some(row) = keys.iter_mut().find(|k| k.key == member.name) {
+                if row.typescript[index].is_none() {
+                    row.typescript[index] = Some(member.clone());
+                }
+                continue;
+            }
+            let mut members = vec![None; typescript.len()];
+            members[index] = Some(member.clone());
+            keys.push(ModelKey {
+                key: member.name.clone(),
+                swift: None,
+                typescript: members,
+            });
+        }
+    }
+
+    let mut issues = Vec::new();
+    let swift_label = format!("{}.{}", swift_package, model.name);
+    for row in &keys {
+        for ((package, export), member) in typescript.iter().zip(&row.typescript) {
+            let ts_label = format!("`{}` in {}", export.name, package);
+            let issue = compare_key(row.swift.as_ref(), member.as_ref(), decodes).map(
+                |(severity, problem)| {
+                    let (file, line) = match (&row.swift, member) {
+                        (Some(property), _) => (model.source_file.clone(), property.line),
+                        (None, Some(member)) => (export.source_file.clone(), member.line),
+                        (None, None) => (model.source_file.clone(), model.line),
+                    };
+                    ValidationIssue {
+                        severity,
+                        message: format!("{} `{}` {} {}", swift_label, row.key, problem, ts_label),
+                        file: Some(file),
+                        line: Some(line),
+                        suggestion: None,
+                    }
+                },
+            );
+            issues.extend(issue);
+        }
+    }
+
+    ModelComparison {
+        swift_package: swift_package.to_string(),
+        swift: model,
+        typescript,
+        keys,
+        issues,
+    }
+}
+
+/// Severity and description of the drift for one key, if any.
+///
+/// Decoding fails when Swift requires a key the server may omit or send as
+/// `null`; encoding fails when Swift may omit a key the server requires.
+fn compare_key(
+    swift: Option<&Property>,
+    ts: Option<&Property>,
+    decodes: bool,
+) -> Option<(IssueSeverity, String)> {
+    let ts_optional = |m: &Property| {
+        m.optional || m.type_annotation.contains("null") || m.type_annotation.contains("undefined")
+    };
+
+    match (swift, ts) {
+        (Some(property), None) if decodes && !property.optional => Some((
+            IssueSeverity::Error,
+            "is required when decoding but not declared by".to_string(),
+        )),
+        (Some(_), None) => Some((IssueSeverity::Warning, "is not declared by".to_string())),
+        (None, Some(member)) if !decodes && !ts_optional(member) => Some((
+            IssueSeverity::Error,
+            "is never sent but required by".to_string(),
+        )),
+        (None, Some(_)) => Some((
+            IssueSeverity::Warning,
+            "is not mapped in Swift but declared by".to_string(),
+        )),
+        (Some(property), Some(member)) => {
+            let swift_optional = property.optional;
+            match (decodes, swift_optional, ts_optional(member)) {
+                (true, false, true) => Some((
+                    IssueSeverity::Error,
+                    "is non-optional in Swift but may be absent or null in".to_string(),
+                )),
+                (true, true, false) => Some((
+                    IssueSeverity::Warning,
+                    "is optional in Swift but always present in".to_string(),
+                )),
+                (false, true, false) => Some((
+                    IssueSeverity::Error,
+                    "may be omitted by Swift but is required by".to_string(),
+                )),
+                _ => None,
+            }
+        }
+        (None, None) => None,
+    }
+}
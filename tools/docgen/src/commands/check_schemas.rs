@@ -0,0 +1,247 @@
+//! Schema drift detection across storage adapters
+//!
+//! The Oracle, Drizzle and MongoDB adapters each define the same logical
+//! `AuthUser`/`RefreshToken` records by hand. This command normalizes every
+//! storage definition and compares it with the core interfaces.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tracing::{error, info};
+
+use super::{generate, validate};
+use crate::extractors::{drizzle, mongoose, sql, typescript};
+use crate::types::{
+    EntityField, EntityModel, Export, ExportKind, IssueSeverity, PackageKind, ValidationIssue,
+    ValidationResult,
+};
+
+/// Core interfaces every adapter has to store
+const CORE_ENTITIES: [&str; 2] = ["AuthUser", "RefreshToken"];
+
+/// Run the schema drift check
+pub async fn run(root: &str, strict: bool) -> Result<()> {
+    let root_path = Path::new(root);
+    let config = generate::load_or_create_config(root_path)?;
+
+    info!("Checking adapter schemas against core types");
+
+    let mut core = Vec::new();
+    let mut storage = Vec::new();
+
+    for pkg in &config.packages {
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root_path.join(&pkg.path)
+        };
+
+        let types_path = pkg_path.join("src").join("types.ts");
+        if pkg.kind == PackageKind::Core && types_path.exists() {
+            for export in typescript::extract_file(&types_path).await? {
+                if export.kind == ExportKind::Interface
+                    && CORE_ENTITIES.contains(&export.name.as_str())
+                {
+                    core.push(interface_entity(&export));
+                }
+            }
+        }
+
+        let adapter = pkg.name.rsplit('-').next().unwrap_or(&pkg.name);
+        storage.extend(storage_models(&pkg_path, adapter)?);
+    }
+
+    let mut result = ValidationResult {
+        passed: true,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        info: Vec::new(),
+    };
+
+    for entity in CORE_ENTITIES {
+        let models: Vec<_> = storage
+            .iter()
+            .filter(|m| logical_entity(&m.entity) == Some(entity))
+            .collect();
+
+        match core.iter().find(|c| c.entity == entity) {
+            Some(core_model) => compare_with_core(core_model, &models, &mut result),
+            None => result
+                .info
+                .push(format!("Core interface {} not found, skipping", entity)),
+        }
+
+        compare_adapters(entity, &models, &mut result);
+
+        result.info.push(format!(
+            "{}: compared {} storage definitions",
+            entity,
+            models.len()
+        ));
+    }
+
+    validate::report_results(&result, strict);
+
+    if !result.errors.is_empty() || (strict && !result.warnings.is_empty()) {
+        result.passed = false;
+    }
+
+    if result.passed {
+        info!("✅ Adapter schemas match the core types!");
+        Ok(())
+    } else {
+        error!("❌ Schema drift detected!");
+        std::process::exit(1);
+    }
+}
+
+/// Collect every storage definition a package ships
+fn storage_models(pkg_path: &Path, adapter: &str) -> Result<Vec<EntityModel>> {
+    let mut models = Vec::new();
+
+    let src = pkg_path.join("src");
+
+    let sql_path = src.join("schema.sql");
+    if sql_path.exists() {
+        models.extend(sql::extract_tables(&sql_path, adapter)?);
+    }
+
+    let schema_dir = src.join("schema");
+    if schema_dir.is_dir() {
+        models.extend(drizzle::extract_tables(&schema_dir)?);
+    }
+
+    let models_dir = src.join("models");
+    if models_dir.is_dir() {
+        models.extend(
+            mongoose::extract_models(&models_dir)?
+                .iter()
+                .map(mongoose::to_entity),
+        );
+    }
+
+    Ok(models)
+}
+
+/// Normalize a core interface into an entity model
+fn interface_entity(export: &Export) -> EntityModel {
+    EntityModel {
+        entity: export.name.clone(),
+        source: format!("core:{}", export.name),
+        file: export.source_file.clone(),
+        line: export.line,
+        fields: export
+            .properties
+            .iter()
+            .filter(|p| !p.method)
+            .map(|p| EntityField {
+                name: p.name.clone(),
+                type_name: p.type_annotation.clone(),
+                nullable: p.optional
+                    || p.type_annotation.contains("null")
+                    || p.type_annotation.contains("undefined"),
+                line: p.line,
+            })
+            .collect(),
+    }
+}
+
+/// Map a table or collection name onto the core entity it stores
+fn logical_entity(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    if name.contains("refresh") || name.contains("token") {
+        Some("RefreshToken")
+    } else if name.contains("user") {
+        Some("AuthUser")
+    } else {
+        None
+    }
+}
+
+/// Every core field must be stored, with compatible nullability
+fn compare_with_core(core: &EntityModel, models: &[&EntityModel], result: &mut ValidationResult) {
+    for model in models {
+        for core_field in &core.fields {
+            let Some(field) = model.fields.iter().find(|f| f.name == core_field.name) else {
+                result.errors.push(ValidationIssue {
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "{} is missing field `{}` of {}",
+                        model.source, core_field.name, core.entity
+                    ),
+                    file: Some(model.file.clone()),
+                    line: Some(model.line),
+                    suggestion: Some(format!(
+                        "Add `{}` (defined at {}:{})",
+                        core_field.name,
+                        core.file.display(),
+                        core_field.line
+                    )),
+                });
+                continue;
+            };
+
+            if field.nullable && !core_field.nullable {
+                result.errors.push(ValidationIssue {
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "`{}` is nullable in {} but required in {}",
+                        field.name, model.source, core.entity
+                    ),
+                    file: Some(model.file.clone()),
+                    line: Some(field.line),
+                    suggestion: Some("Add a NOT NULL constraint or a default".to_string()),
+                });
+            } else if !field.nullable && core_field.nullable {
+                result.warnings.push(ValidationIssue {
+                    severity: IssueSeverity::Warning,
+                    message: format!(
+                        "`{}` is NOT NULL in {} but optional in {}",
+                        field.name, model.source, core.entity
+                    ),
+                    file: Some(model.file.clone()),
+                    line: Some(field.line),
+                    suggestion: Some(format!(
+                        "Make the column nullable or the field required in {}",
+                        core.entity
+                    )),
+                });
+            }
+        }
+    }
+}
+
+/// Fields stored by some adapters but not others (e.g., lockout columns)
+fn compare_adapters(entity: &str, models: &[&EntityModel], result: &mut ValidationResult) {
+    let all_fields: BTreeSet<&str> = models
+        .iter()
+        .flat_map(|m| m.fields.iter().map(|f| f.name.as_str()))
+        .collect();
+
+    for name in all_fields {
+        let (present, missing): (Vec<&&EntityModel>, Vec<&&EntityModel>) = models
+            .iter()
+            .partition(|m| m.fields.iter().any(|f| f.name == name));
+
+        if missing.is_empty() {
+            continue;
+        }
+
+        let present: Vec<_> = present.iter().map(|m| m.source.as_str()).collect();
+        for model in missing {
+            result.warnings.push(ValidationIssue {
+                severity: IssueSeverity::Warning,
+                message: format!(
+                    "{} has no `{}` field for {} (present in {})",
+                    model.source,
+                    name,
+                    entity,
+                    present.join(", ")
+                ),
+                file: Some(model.file.clone()),
+                line: Some(model.line),
+                suggestion: None,
+            });
+        }
+    }
+}
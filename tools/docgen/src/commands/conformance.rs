@@ -0,0 +1,212 @@
+//! Adapter conformance with the core repository interfaces
+//!
+//! The Oracle, Drizzle and MongoDB adapters implement `UserRepository` and
+//! `RefreshTokenRepository` from `fastify-apple-auth` by hand. This module
+//! pairs every interface with the adapter classes that `implements` it and
+//! checks each required method is present with a compatible signature.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::extractors::typescript::{self, matching_delimiter, split_top_level};
+use crate::types::{
+    Conformance, ConformanceRow, DocgenConfig, Export, ExportKind, IssueSeverity, MethodStatus,
+    PackageKind, Property, ValidationIssue,
+};
+
+/// Pair every core repository interface with its adapter implementations
+pub async fn collect(root: &Path, config: &DocgenConfig) -> Result<Vec<Conformance>> {
+    let mut interfaces: Vec<(String, Export)> = Vec::new();
+    let mut classes: Vec<(String, Export)> = Vec::new();
+
+    for pkg in &config.packages {
+        if !matches!(pkg.kind, PackageKind::Core | PackageKind::Adapter) {
+            continue;
+        }
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root.join(&pkg.path)
+        };
+
+        for file in typescript::source_files(&pkg_path) {
+            for export in typescript::extract_file(&file).await? {
+                match (&pkg.kind, &export.kind) {
+                    (PackageKind::Core, ExportKind::Interface) => {
+                        interfaces.push((pkg.name.clone(), export))
+                    }
+                    (PackageKind::Adapter, ExportKind::Class) if !export.implements.is_empty() => {
+                        classes.push((pkg.name.clone(), export))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut reports = Vec::new();
+    for (package, interface) in interfaces {
+        let implementations: Vec<_> = classes
+            .iter()
+            .filter(|(_, class)| class.implements.contains(&interface.name))
+            .cloned()
+            .collect();
+        if implementations.is_empty() {
+            continue;
+        }
+        reports.push(check(package, interface, implementations));
+    }
+
+    Ok(reports)
+}
+
+/// Check every implementation against the interface's methods
+fn check(
+    package: String,
+    interface: Export,
+    implementations: Vec<(String, Export)>,
+) -> Conformance {
+    let mut methods = Vec::new();
+    let mut issues = Vec::new();
+
+    for required in interface.properties.iter().filter(|m| m.method) {
+        let mut status = Vec::new();
+        for (pkg, class) in &implementations {
+            let found = class.properties.iter().find(|m| m.name == required.name);
+            let label = format!("{}.{} in {}", class.name, required.name, pkg);
+
+            let (state, issue) = match found {
+                None if required.optional => (MethodStatus::Omitted, None),
+                None => (
+                    MethodStatus::Missing,
+                    Some(ValidationIssue {
+                        severity: IssueSeverity::Error,
+                        message: format!("{} is missing, required by `{}`", label, interface.name),
+                        file: Some(class.source_file.clone()),
+                        line: Some(class.line),
+                        suggestion: Some(format!(
+                            "Implement `{}{}`",
+                            required.name, required.type_annotation
+                        )),
+                    }),
+                ),
+                Some(method) => match signature_mismatch(required, method) {
+                    None => (MethodStatus::Implemented, None),
+                    Some(problem) => (
+                        MethodStatus::Mismatch,
+                        Some(ValidationIssue {
+                            severity: IssueSeverity::Error,
+                            message: format!(
+                                "{} {}: `{}` does not match `{}` in `{}`",
+                                label,
+                                problem,
+                                method.type_annotation,
+                                required.type_annotation,
+                                interface.name
+                            ),
+                            file: Some(class.source_file.clone()),
+                            line: Some(method.line),
+                            suggestion: None,
+                        }),
+                    ),
+                },
+            };
+
+            status.push(state);
+            issues.extend(issue);
+        }
+
+        methods.push(ConformanceRow {
+            method: required.clone(),
+            status,
+        });
+    }
+
+    Conformance {
+        package,
+        interface,
+        implementations,
+        methods,
+        issues,
+    }
+}
+
+/// Why `method` cannot stand in for `required`, if it cannot.
+///
+/// Parameter names may differ; types are compared after whitespace
+/// normalization. Extra parameters must be optional. A class method without a return annotation is accepted.
+fn signature_mismatch(required: &Property, method: &Property) -> Option<String> {
+    let (expected_params, expected_returns) = split_signature(&required.type_annotation)?;
+    let (params, returns) = split_signature(&method.type_annotation)?;
+
+    // Taking fewer parameters is fine: TypeScript ignores the extra arguments
+    for (i, (expected, param)) in expected_params.iter().zip(&params).enumerate() {
+        if param.type_text != expected.type_text {
+            return Some(format!("has a different type for parameter {}", i + 1));
+        }
+    }
+    if let Some(extra) = params
+        .iter()
+        .skip(expected_params.len())
+        .find(|p| !p.optional)
+    {
+        return Some(format!("requires extra parameter `{}`", extra.name));
+    }
+
+    match (expected_returns, returns) {
+        (Some(expected), Some(actual)) if expected != actual => {
+            Some("has a different return type".to_string())
+        }
+        _ => None,
+    }
+}
+
+struct Param {
+    name: String,
+    type_text: String,
+    optional: bool,
+}
+
+/// Split `<T>(a: string, b?: number): R` into parameters and return type
+fn split_signature(signature: &str) -> Option<(Vec<Param>, Option<String>)> {
+    let open = signature.find('(')?;
+    let close = matching_delimiter(signature, open)?;
+
+    let params = split_top_level(&signature[open + 1..close], ',')
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (decl, default) = match split_top_level(p, '=').as_slice() {
+                [decl, ..] if p.contains('=') && !p.contains("=>") => (decl.clone(), true),
+                _ => (p.to_string(), false),
+            };
+            let (name, type_text) = match split_top_level(&decl, ':').split_first() {
+                Some((name, rest)) if !rest.is_empty() => (name.clone(), rest.join(":")),
+                _ => (decl.clone(), "any".to_string()),
+            };
+            let name = name
+                .trim()
+                .trim_start_matches("readonly ")
+                .trim_start_matches("...")
+                .to_string();
+            Param {
+                optional: default || name.ends_with('?'),
+                name: name.trim_end_matches('?').to_string(),
+                type_text: normalize(&type_text),
+            }
+        })
+        .collect();
+
+    let returns = signature[close + 1..]
+        .trim()
+        .strip_prefix(':')
+        .map(normalize)
+        .filter(|r| !r.is_empty());
+
+    Some((params, returns))
+}
+
+fn normalize(type_text: &str) -> String {
+    type_text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
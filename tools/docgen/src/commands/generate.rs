@@ -2,15 +2,29 @@
 
 use anyhow::{Context, Result};
 use std::path::Path;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 use walkdir::WalkDir;
 
-use crate::extractors::typescript;
-use crate::generators::markdown;
-use crate::types::{DocgenConfig, PackageConfig, PackageKind};
+use super::{check_models, conformance};
+use crate::extractors::{deps, examples, swift, typescript};
+use crate::generators::links::SourceLinks;
+use crate::generators::output::Output;
+use crate::generators::{embed, markdown, nav};
+use crate::generators::templates::Templates;
+use crate::types::{
+    DependencyEdge, DependencyGraph, DocgenConfig, ExampleRegion, ExtractedDocs, OutputConfig,
+    PackageConfig, PackageKind,
+};
 
-/// Run documentation generation
-pub async fn run(root: &str, output: &str, package_filter: Option<&str>) -> Result<()> {
+/// Run documentation generation, or with `check` only compare what would
+/// be written with the files on disk
+pub async fn run(
+    root: &str,
+    output: &str,
+    package_filter: Option<&str>,
+    check: bool,
+    force: bool,
+) -> Result<()> {
     let root_path = Path::new(root);
     let output_path = Path::new(output);
 
@@ -24,7 +38,7 @@ pub async fn run(root: &str, output: &str, package_filter: Option<&str>) -> Resu
     let packages: Vec<_> = config
         .packages
         .iter()
-        .filter(|p| package_filter.map_or(true, |f| p.name.contains(f)))
+        .filter(|p| package_filter.is_none_or(|f| p.name.contains(f)))
         .collect();
 
     if packages.is_empty() {
@@ -34,27 +48,160 @@ pub async fn run(root: &str, output: &str, package_filter: Option<&str>) -> Resu
 
     info!("Processing {} packages", packages.len());
 
+    // Example regions from every package, so filtered runs still resolve them
+    let (regions, problems) = examples::collect(root_path, &config)?;
+    for problem in &problems {
+        warn!("{}", problem.message);
+    }
+
+    // Dependencies between the packages, including those filtered out
+    let graph = DependencyGraph {
+        edges: deps::dependency_graph(root_path, &config)?,
+        references: deps::type_references(root_path, &config)?,
+    };
+
+    // Page templates, with the project's overrides
+    let templates_dir = config.templates.as_ref().map(|dir| root_path.join(dir));
+    let templates = Templates::load(templates_dir.as_deref(), config.output.flavor)?;
+
+    // Source links into the repository at the checked out ref
+    let links = SourceLinks::discover(root_path, &config);
+
+    let api_dir = output_path.join("api");
+    let mut out = Output::new(output_path, check, force)?;
+    let mut extracted = Vec::new();
     for pkg in packages {
         info!("Processing package: {}", pkg.name);
-        process_package(root_path, output_path, pkg).await?;
+        let docs = process_package(root_path, pkg, &regions, &graph.edges, &config.output).await?;
+
+        // Generate markdown documentation
+        let output_dir = api_dir.join(pkg.name.replace("@acedergren/", "").replace("-", "_"));
+        markdown::generate_package_docs(
+            &output_dir,
+            &docs,
+            &templates,
+            &config.output,
+            &links,
+            &mut out,
+        )
+        .await?;
+        extracted.push(docs);
+    }
+
+    // Generated regions in the hand-written pages, which may use any package
+    let mut problems = Vec::new();
+    if package_filter.is_none() {
+        problems = embed::update(&extracted, &regions, &templates, &links, &mut out)?;
+        for problem in &problems {
+            warn!(
+                "{}:{}: {}",
+                problem.file.as_deref().unwrap_or(output_path).display(),
+                problem.line.unwrap_or(0),
+                problem.message
+            );
+        }
+    }
+
+    // Every package's releases in one date-sorted changelog, unless the
+    // site has its own that the releases are spliced into
+    if package_filter.is_none() && config.output.changelog {
+        match markdown::changelog_page(output_path) {
+            _ if extracted.iter().all(|d| d.releases.is_empty()) => {
+                warn!("No package has a CHANGELOG.md, so there are no releases to document")
+            }
+            None => warn!(
+                "Add a `<!-- docgen:begin changelog -->` region to {} to list the package releases",
+                output_path.join("changelog.md").display()
+            ),
+            Some(page) if page.starts_with(&api_dir) => {
+                markdown::generate_changelog_index(&mut out, &extracted).await?
+            }
+            // Spliced into the site's page with the other regions
+            Some(_) => {}
+        }
+    }
+
+    // Security controls claimed in source comments, across packages
+    if package_filter.is_none() && extracted.iter().any(|d| !d.security_notes.is_empty()) {
+        markdown::generate_security_index(&mut out, &extracted, &links.for_page(&api_dir)).await?;
+    }
+
+    // Compare the Swift models with the TypeScript interfaces
+    let models = if package_filter.is_none() {
+        check_models::collect(root_path, &config).await?
+    } else {
+        Vec::new()
+    };
+    if !models.is_empty() {
+        markdown::generate_models_doc(&mut out, &models, &links.for_page(&api_dir)).await?;
+    }
+
+    // Adapter conformance with the core repository interfaces
+    let conformance = if package_filter.is_none() {
+        conformance::collect(root_path, &config).await?
+    } else {
+        Vec::new()
+    };
+    if !conformance.is_empty() {
+        markdown::generate_conformance_doc(&mut out, &conformance, &links.for_page(&api_dir))
+            .await?;
+    }
+
+    // Generate index/overview pages, linking the cross-package pages only
+    // when every package was processed
+    let all_docs = if package_filter.is_none() {
+        extracted.as_slice()
+    } else {
+        &[]
+    };
+    markdown::generate_index(
+        &mut out,
+        &config,
+        all_docs,
+        &models,
+        &conformance,
+        &graph,
+        &templates,
+    )
+    .await?;
+
+    // List the pages in the mkdocs nav; a filtered run only knows some
+    if package_filter.is_none() {
+        nav::update(root_path, &config, &mut out)?;
     }
 
-    // Generate index/overview pages
-    markdown::generate_index(output_path, &config).await?;
+    // Record the pages, removing those of packages and exports that are
+    // gone; a filtered run only knows some
+    out.finish(package_filter.is_none())?;
+
+    if check {
+        for path in out.stale() {
+            error!("Out of date: {}", path.display());
+        }
+        if !out.stale().is_empty() || !problems.is_empty() {
+            error!("❌ Documentation is out of date, run `docgen generate`");
+            std::process::exit(1);
+        }
+        info!("✅ Documentation is up to date");
+        return Ok(());
+    }
 
     info!("Documentation generation complete!");
     Ok(())
 }
 
 /// Load config from docgen.yaml or create default
-fn load_or_create_config(root: &Path) -> Result<DocgenConfig> {
+pub fn load_or_create_config(root: &Path) -> Result<DocgenConfig> {
     let config_path = root.join("docgen.yaml");
 
     if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path)
-            .context("Failed to read docgen.yaml")?;
-        let config: DocgenConfig = serde_yaml::from_str(&content)
-            .context("Failed to parse docgen.yaml")?;
+        let content =
+            std::fs::read_to_string(&config_path).context("Failed to read docgen.yaml")?;
+        let mut config: DocgenConfig =
+            serde_yaml::from_str(&content).context("Failed to parse docgen.yaml")?;
+        if config.packages.is_empty() {
+            config.packages = discover_packages(root)?;
+        }
         return Ok(config);
     }
 
@@ -64,13 +211,15 @@ fn load_or_create_config(root: &Path) -> Result<DocgenConfig> {
 
     Ok(DocgenConfig {
         packages,
-        output: crate::types::OutputConfig {
+        output: OutputConfig {
             dir: root.join("docs"),
-            api_reference: true,
-            changelog: true,
-            package_readme: true,
+            ..OutputConfig::default()
         },
         templates: None,
+        repo_url: None,
+        source_ref: None,
+        model_map: Vec::new(),
+        known_drift: Vec::new(),
     })
 }
 
@@ -83,6 +232,7 @@ fn discover_packages(root: &Path) -> Result<Vec<PackageConfig>> {
     if packages_dir.exists() {
         for entry in WalkDir::new(&packages_dir)
             .max_depth(2)
+            .sort_by_file_name()
             .into_iter()
             .filter_map(|e| e.ok())
         {
@@ -95,6 +245,20 @@ fn discover_packages(root: &Path) -> Result<Vec<PackageConfig>> {
         }
     }
 
+    // Swift packages have a Package.swift manifest instead
+    for (name, path) in swift::find_packages(root)? {
+        if !path.join("package.json").exists() {
+            packages.push(PackageConfig {
+                name,
+                path,
+                kind: PackageKind::Mobile,
+                entry_points: Vec::new(),
+                exclude: Vec::new(),
+                repository: None,
+            });
+        }
+    }
+
     Ok(packages)
 }
 
@@ -124,7 +288,7 @@ fn parse_package_json(path: &Path) -> Result<Option<PackageConfig>> {
     let mut entry_points = vec!["src/index.ts".to_string()];
     if let Some(exports) = pkg.get("exports") {
         if let Some(obj) = exports.as_object() {
-            for (key, value) in obj {
+            for value in obj.values() {
                 if let Some(import_path) = value.get("import").and_then(|v| v.as_str()) {
                     if !entry_points.contains(&import_path.to_string()) {
                         entry_points.push(import_path.replace("./", ""));
@@ -145,15 +309,18 @@ fn parse_package_json(path: &Path) -> Result<Option<PackageConfig>> {
             "**/test/**".to_string(),
             "**/tests/**".to_string(),
         ],
+        repository: None,
     }))
 }
 
-/// Process a single package
+/// Extract the documentation of a single package
 async fn process_package(
     root: &Path,
-    output: &Path,
     config: &PackageConfig,
-) -> Result<()> {
+    regions: &[ExampleRegion],
+    dependencies: &[DependencyEdge],
+    options: &OutputConfig,
+) -> Result<ExtractedDocs> {
     let pkg_path = if config.path.is_absolute() {
         config.path.clone()
     } else {
@@ -161,16 +328,19 @@ async fn process_package(
     };
 
     // Extract TypeScript documentation
-    let extracted = typescript::extract_package(&pkg_path, config).await?;
-
-    // Generate markdown documentation
-    let output_dir = output.join("api").join(
-        config.name
-            .replace("@acedergren/", "")
-            .replace("-", "_")
-    );
-
-    markdown::generate_package_docs(&output_dir, &extracted).await?;
+    let mut extracted = typescript::extract_package(&pkg_path, config).await?;
+    examples::apply(&mut extracted, regions);
+    if !options.changelog {
+        extracted.releases.clear();
+    }
+    for edge in dependencies {
+        if edge.from == config.name {
+            extracted.package.internal_deps.push(edge.to.clone());
+        }
+        if edge.to == config.name {
+            extracted.package.dependents.push(edge.from.clone());
+        }
+    }
 
-    Ok(())
+    Ok(extracted)
 }
@@ -1,5 +1,9 @@
 //! CLI command implementations
 
+pub mod check_models;
+pub mod check_schemas;
+pub mod conformance;
 pub mod generate;
+pub mod openapi;
 pub mod validate;
 pub mod watch;
@@ -0,0 +1,94 @@
+//! OpenAPI document generation
+//!
+//! Writes the HTTP contract of the auth server as OpenAPI 3.1 so clients and
+//! mock servers can be generated from the same source as the markdown docs.
+//! The document is owned like a generated page, in the manifest of the
+//! docs directory when it is written there.
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::{error, info, warn};
+
+use super::generate;
+use crate::extractors::typescript;
+use crate::generators::links::absolute;
+use crate::generators::openapi;
+use crate::generators::output::Output;
+
+/// Run the openapi command
+pub async fn run(
+    root: &str,
+    output: &str,
+    package_filter: Option<&str>,
+    check: bool,
+    force: bool,
+) -> Result<()> {
+    let root_path = Path::new(root);
+    let output_path = Path::new(output);
+    let config = generate::load_or_create_config(root_path)?;
+
+    // Every package is extracted so response types can resolve across packages
+    let mut docs = Vec::new();
+    for pkg in &config.packages {
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root_path.join(&pkg.path)
+        };
+        docs.push(typescript::extract_package(&pkg_path, pkg).await?);
+    }
+
+    let route_count: usize = docs
+        .iter()
+        .filter(|d| package_filter.is_none_or(|f| d.package.name.contains(f)))
+        .map(|d| d.routes.len())
+        .sum();
+    if route_count == 0 {
+        warn!("No HTTP routes found, skipping OpenAPI generation");
+        return Ok(());
+    }
+
+    let document = openapi::build_document(&docs, package_filter);
+
+    let is_yaml = output_path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+    let content = if is_yaml {
+        serde_yaml::to_string(&document)?
+    } else {
+        serde_json::to_string_pretty(&document)? + "\n"
+    };
+
+    // Recorded in the manifest `docgen generate` keeps in the docs
+    // directory, which does not prune it
+    let docs_dir = absolute(&root_path.join(&config.output.dir));
+    let output_path = absolute(output_path);
+    let dir = if output_path.starts_with(&docs_dir) {
+        docs_dir.as_path()
+    } else {
+        output_path.parent().unwrap_or(Path::new("."))
+    };
+    let mut out = Output::new(dir, check, force)?.written_by("openapi");
+    out.write(&output_path, &content)?;
+    out.finish(false)?;
+
+    if check {
+        for path in out.stale() {
+            error!("Out of date: {}", path.display());
+        }
+        if !out.stale().is_empty() {
+            error!("❌ OpenAPI document is out of date, run `docgen openapi`");
+            std::process::exit(1);
+        }
+        info!("✅ {} is up to date", output_path.display());
+        return Ok(());
+    }
+
+    info!(
+        "Generated {} ({} operations)",
+        output_path.display(),
+        route_count
+    );
+
+    Ok(())
+}
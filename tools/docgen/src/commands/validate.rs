@@ -5,7 +5,11 @@ use std::path::Path;
 use tracing::{error, info, warn};
 use walkdir::WalkDir;
 
-use crate::types::{IssueSeverity, ValidationIssue, ValidationResult};
+use super::{conformance, generate};
+use crate::extractors::{examples, fastify, fetch, swift, typescript, zod};
+use crate::types::{
+    ClientEndpoint, IssueSeverity, PackageKind, Route, ValidationIssue, ValidationResult, ZodSchema,
+};
 
 /// Run documentation validation
 pub async fn run(root: &str, strict: bool) -> Result<()> {
@@ -36,7 +40,7 @@ pub async fn run(root: &str, strict: bool) -> Result<()> {
         for entry in WalkDir::new(&docs_path)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "md"))
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
         {
             validate_markdown_file(entry.path(), &mut result)?;
         }
@@ -51,6 +55,15 @@ pub async fn run(root: &str, strict: bool) -> Result<()> {
         validate_mkdocs_config(root_path, &mut result)?;
     }
 
+    // Check client SDK endpoints against the server routes
+    check_client_endpoints(root_path, &mut result)?;
+
+    // Check adapter classes implement the core repository interfaces
+    check_conformance(root_path, &mut result).await?;
+
+    // Check example regions and the references to them
+    check_example_regions(root_path, &docs_path, &mut result)?;
+
     // Report results
     report_results(&result, strict);
 
@@ -152,7 +165,7 @@ fn check_internal_links(docs_path: &Path, result: &mut ValidationResult) -> Resu
     for entry in WalkDir::new(docs_path)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "md"))
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
     {
         let content = std::fs::read_to_string(entry.path())?;
         let file_dir = entry.path().parent().unwrap_or(docs_path);
@@ -166,14 +179,15 @@ fn check_internal_links(docs_path: &Path, result: &mut ValidationResult) -> Resu
             }
 
             // Resolve relative path
-            let target = if link.starts_with('/') {
-                docs_path.join(&link[1..])
+            let target = if let Some(absolute) = link.strip_prefix('/') {
+                docs_path.join(absolute)
             } else {
                 file_dir.join(link)
             };
 
             // Remove anchor from path
-            let target_path = target.to_string_lossy().split('#').next().unwrap_or("");
+            let target_str = target.to_string_lossy();
+            let target_path = target_str.split('#').next().unwrap_or("");
             let target_path = Path::new(target_path);
 
             // Check if file exists (with or without .md extension)
@@ -229,8 +243,368 @@ fn validate_mkdocs_config(root: &Path, result: &mut ValidationResult) -> Result<
     Ok(())
 }
 
+/// Diff the paths, methods and body keys hardcoded in the Swift and web
+/// clients against the routes the Fastify server registers
+fn check_client_endpoints(root: &Path, result: &mut ValidationResult) -> Result<()> {
+    let config = generate::load_or_create_config(root)?;
+
+    let mut routes = Vec::new();
+    let mut schemas = Vec::new();
+    let mut endpoints = Vec::new();
+
+    for pkg in &config.packages {
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root.join(&pkg.path)
+        };
+        let sources = typescript::source_files(&pkg_path);
+
+        match pkg.kind {
+            PackageKind::Frontend => {
+                endpoints.extend(fetch::extract_endpoints(&sources, &pkg.name)?);
+            }
+            PackageKind::Core => {
+                routes.extend(fastify::extract_routes(&pkg_path, &sources)?);
+                for source in &sources {
+                    schemas.extend(zod::extract_schemas(source)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Swift packages have no package.json, so they are found by manifest
+    for (name, pkg_path) in swift::find_packages(root)? {
+        endpoints.extend(swift::extract_endpoints(&pkg_path, &name)?);
+    }
+
+    if routes.is_empty() || endpoints.is_empty() {
+        return Ok(());
+    }
+
+    let before = result.errors.len();
+    let mut matched = vec![false; config.known_drift.len()];
+    for endpoint in &endpoints {
+        let errors = result.errors.len();
+        check_endpoint(endpoint, &routes, &schemas, result);
+
+        // Known mismatches are only warned about until they are fixed
+        let Some(index) = config
+            .known_drift
+            .iter()
+            .position(|k| k.client == endpoint.client && k.path == endpoint.path)
+        else {
+            continue;
+        };
+        for mut issue in result.errors.drain(errors..).collect::<Vec<_>>() {
+            matched[index] = true;
+            issue.severity = IssueSeverity::Warning;
+            issue.message = format!(
+                "{} (known drift: {})",
+                issue.message, config.known_drift[index].reason
+            );
+            result.warnings.push(issue);
+        }
+    }
+    if result.errors.len() > before {
+        result.passed = false;
+    }
+
+    for (known, _) in config.known_drift.iter().zip(matched).filter(|(_, m)| !m) {
+        result.warnings.push(ValidationIssue {
+            severity: IssueSeverity::Warning,
+            message: format!(
+                "Known drift for {} `{}` no longer occurs",
+                known.client, known.path
+            ),
+            file: Some(root.join("docgen.yaml")),
+            line: None,
+            suggestion: Some("Remove it from `known_drift`".to_string()),
+        });
+    }
+
+    result.info.push(format!(
+        "Checked {} client endpoints against {} server routes",
+        endpoints.len(),
+        routes.len()
+    ));
+
+    Ok(())
+}
+
+/// Report adapter repository methods that are missing or whose signature
+/// does not match the core interface
+async fn check_conformance(root: &Path, result: &mut ValidationResult) -> Result<()> {
+    let config = generate::load_or_create_config(root)?;
+
+    for report in conformance::collect(root, &config).await? {
+        for issue in report.issues {
+            result.errors.push(issue);
+            result.passed = false;
+        }
+        result.info.push(format!(
+            "Checked {} implementations of {}",
+            report.implementations.len(),
+            report.interface.name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Report broken example region markers and `{@include}`/
+/// `<!-- docgen:example -->` references to regions that do not exist
+fn check_example_regions(
+    root: &Path,
+    docs_path: &Path,
+    result: &mut ValidationResult,
+) -> Result<()> {
+    let config = generate::load_or_create_config(root)?;
+    let (regions, problems) = examples::collect(root, &config)?;
+    if !problems.is_empty() {
+        result.passed = false;
+    }
+    result.errors.extend(problems);
+
+    let mut files = Vec::new();
+    for pkg in &config.packages {
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root.join(&pkg.path)
+        };
+        files.extend(typescript::source_files(&pkg_path));
+        files.push(pkg_path.join("README.md"));
+    }
+    if docs_path.exists() {
+        files.extend(
+            WalkDir::new(docs_path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+                .map(|e| e.into_path()),
+        );
+    }
+
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        for (name, line) in examples::referenced_names(&content) {
+            if regions.iter().any(|r| r.name == name) {
+                continue;
+            }
+            result.errors.push(ValidationIssue {
+                severity: IssueSeverity::Error,
+                message: format!("Unknown example region `{}`", name),
+                file: Some(file.clone()),
+                line: Some(line),
+                suggestion: Some(format!(
+                    "Mark the code with `// docgen:example {}` ... `// docgen:end`",
+                    name
+                )),
+            });
+            result.passed = false;
+        }
+    }
+
+    result
+        .info
+        .push(format!("Found {} example regions", regions.len()));
+
+    Ok(())
+}
+
+fn check_endpoint(
+    endpoint: &ClientEndpoint,
+    routes: &[Route],
+    schemas: &[ZodSchema],
+    result: &mut ValidationResult,
+) {
+    let label = match &endpoint.name {
+        Some(name) => format!("{} ({})", endpoint.client, name),
+        None => endpoint.client.clone(),
+    };
+    let issue = |message: String, suggestion: Option<String>| ValidationIssue {
+        severity: IssueSeverity::Error,
+        message,
+        file: Some(endpoint.source_file.clone()),
+        line: Some(endpoint.line),
+        suggestion,
+    };
+
+    let candidates: Vec<_> = routes
+        .iter()
+        .filter(|r| client_path_matches(&endpoint.path, &r.full_path()))
+        .collect();
+
+    if candidates.is_empty() {
+        let known = routes
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.full_path()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.errors.push(issue(
+            format!(
+                "{} uses `{}` but no server route matches",
+                label, endpoint.path
+            ),
+            Some(format!("Server routes: {}", known)),
+        ));
+        return;
+    }
+
+    let Some(method) = &endpoint.method else {
+        return;
+    };
+    let Some(route) = candidates.iter().find(|r| &r.method == method) else {
+        let methods = candidates
+            .iter()
+            .map(|r| r.method.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.errors.push(issue(
+            format!(
+                "{} sends {} `{}` but the server only accepts {}",
+                label, method, endpoint.path, methods
+            ),
+            None,
+        ));
+        return;
+    };
+
+    let Some(sent) = &endpoint.body_fields else {
+        return;
+    };
+
+    // Accepted body keys and whether each is required
+    let schema = route
+        .body_schema
+        .as_ref()
+        .and_then(|name| schemas.iter().find(|s| &s.name == name));
+    let accepted: Option<Vec<(String, bool)>> = match (schema, &route.body_type) {
+        (Some(schema), _) => Some(
+            schema
+                .fields
+                .iter()
+                .map(|f| (f.name.clone(), !f.optional))
+                .collect(),
+        ),
+        (None, Some(body_type)) => body_type
+            .trim()
+            .strip_prefix('{')
+            .and_then(|b| b.strip_suffix('}'))
+            .map(|b| {
+                typescript::parse_members(b, 0)
+                    .into_iter()
+                    .map(|m| (m.name, !m.optional))
+                    .collect()
+            }),
+        (None, None) => None,
+    };
+    let route_label = format!("{} {}", route.method, route.full_path());
+
+    let Some(accepted) = accepted else {
+        if !sent.is_empty() {
+            result.errors.push(issue(
+                format!(
+                    "{} sends body fields {} but `{}` does not read a request body",
+                    label,
+                    quote_list(sent),
+                    route_label
+                ),
+                Some(
+                    "Check how the server expects this value (cookie, header or body)".to_string(),
+                ),
+            ));
+        }
+        return;
+    };
+
+    let unknown: Vec<_> = sent
+        .iter()
+        .filter(|f| !accepted.iter().any(|(name, _)| name == *f))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        let strict = schema.is_some_and(|s| s.strict);
+        let message = format!(
+            "{} sends body fields {} not accepted by `{}`{}",
+            label,
+            quote_list(&unknown),
+            route_label,
+            if strict {
+                " (rejected by strict schema)"
+            } else {
+                ""
+            }
+        );
+        let mut found = issue(message, None);
+        if strict {
+            result.errors.push(found);
+        } else {
+            found.severity = IssueSeverity::Warning;
+            result.warnings.push(found);
+        }
+    }
+
+    let missing: Vec<_> = accepted
+        .iter()
+        .filter(|(name, required)| *required && !sent.contains(name))
+        .map(|(name, _)| name.clone())
+        .collect();
+    if !missing.is_empty() {
+        result.errors.push(issue(
+            format!(
+                "{} does not send required body fields {} of `{}`",
+                label,
+                quote_list(&missing),
+                route_label
+            ),
+            None,
+        ));
+    }
+}
+
+/// Whether a client path (possibly under a base path such as `/api/v1`)
+/// ends with the server route's segments; parameters match anything
+fn client_path_matches(client: &str, server: &str) -> bool {
+    let segments = |path: &str| -> Vec<String> {
+        path.split('?')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    let is_param =
+        |s: &str| s.starts_with(':') || s.starts_with('{') || s.contains("${") || s.contains("\\(");
+
+    let client = segments(client);
+    let server = segments(server);
+    if server.len() > client.len() {
+        return false;
+    }
+
+    client
+        .iter()
+        .rev()
+        .zip(server.iter().rev())
+        .all(|(c, s)| c == s || is_param(c) || is_param(s))
+}
+
+fn quote_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| format!("`{}`", n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Report validation results
-fn report_results(result: &ValidationResult, strict: bool) {
+pub fn report_results(result: &ValidationResult, strict: bool) {
     if !result.errors.is_empty() {
         error!("\n❌ Errors ({}):", result.errors.len());
         for issue in &result.errors {
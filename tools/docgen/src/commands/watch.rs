@@ -1,7 +1,6 @@
 //! File watching command for live documentation regeneration
 
 use anyhow::Result;
-use std::path::Path;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{info, warn};
@@ -16,7 +15,7 @@ pub async fn run(root: &str, output: &str) -> Result<()> {
     info!("Press Ctrl+C to stop");
 
     // Initial generation
-    if let Err(e) = generate::run(root, output, None).await {
+    if let Err(e) = generate::run(root, output, None, false, false).await {
         warn!("Initial generation failed: {}", e);
     }
 
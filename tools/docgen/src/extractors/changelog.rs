@@ -0,0 +1,78 @@
+//! Changelog parser
+//!
+//! Parses Keep a Changelog files (`## [1.2.0] - 2024-01-15` releases with
+//! `### Added`/`### Fixed` sections) and the Changesets variant
+//! (`## 1.2.0` with `### Minor Changes` and `- abc1234: message` entries)
+//! into versioned release entries.
+
+use crate::types::{Release, ReleaseSection};
+
+/// Parse the releases of a changelog, newest first as written
+pub fn parse(content: &str) -> Vec<Release> {
+    let release_re = regex::Regex::new(
+        r"^##\s+\[?(Unreleased|v?\d+\.\d+\.\d+[\w.+-]*)\]?(?:\s*[-–—]\s*|\s+\()?(\d{4}-\d{2}-\d{2})?\)?\s*(\[YANKED\])?\s*$",
+    )
+    .expect("valid release regex");
+    let hash_re = regex::Regex::new(r"^[0-9a-f]{7,40}:\s*").expect("valid hash regex");
+
+    let mut releases: Vec<Release> = Vec::new();
+    // Whether lines belong to the last release; other `##` sections end it
+    let mut in_release = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_end();
+
+        if trimmed.starts_with("## ") {
+            in_release = false;
+            if let Some(cap) = release_re.captures(trimmed) {
+                in_release = true;
+                releases.push(Release {
+                    version: cap[1].trim_start_matches('v').to_string(),
+                    date: cap.get(2).map(|d| d.as_str().to_string()),
+                    yanked: cap.get(3).is_some(),
+                    sections: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        let Some(release) = releases.last_mut().filter(|_| in_release) else {
+            continue;
+        };
+
+        if trimmed == "---" {
+            in_release = false;
+        } else if let Some(heading) = trimmed.strip_prefix("### ") {
+            release.sections.push(ReleaseSection {
+                heading: heading.trim().to_string(),
+                entries: Vec::new(),
+            });
+        } else if let Some(entry) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            if release.sections.is_empty() {
+                release.sections.push(ReleaseSection {
+                    heading: "Changes".to_string(),
+                    entries: Vec::new(),
+                });
+            }
+            let entry = hash_re.replace(entry.trim(), "").to_string();
+            release.sections.last_mut().unwrap().entries.push(entry);
+        } else if !trimmed.trim().is_empty() && line.starts_with([' ', '\t']) {
+            // Wrapped or nested lines continue the previous entry
+            if let Some(last) = release
+                .sections
+                .last_mut()
+                .and_then(|s| s.entries.last_mut())
+            {
+                last.push(' ');
+                last.push_str(trimmed.trim().trim_start_matches("- "));
+            }
+        }
+    }
+
+    // An empty `[Unreleased]` placeholder is not a release
+    releases.retain(|r| r.version != "Unreleased" || !r.sections.is_empty());
+    releases
+}
@@ -0,0 +1,188 @@
+//! Monorepo dependency extractor
+//!
+//! Resolves the dependencies between configured packages from
+//! `package.json` (`dependencies`, `peerDependencies` and `workspace:`
+//! specifiers, including dev dependencies) and `Package.swift` (`.package(path:)` and
+//! `.package(name:)`), and the cross-package imports in their sources.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::typescript::{self, blank_comments};
+use crate::types::{DependencyEdge, DependencyKind, DocgenConfig, TypeReference};
+
+/// Dependency fields read from `package.json`, in order of precedence
+const DEPENDENCY_FIELDS: [(&str, DependencyKind); 4] = [
+    ("dependencies", DependencyKind::Runtime),
+    ("optionalDependencies", DependencyKind::Runtime),
+    ("peerDependencies", DependencyKind::Peer),
+    ("devDependencies", DependencyKind::Dev),
+];
+
+/// Edges between the configured packages, `from` depending on `to`
+pub fn dependency_graph(root: &Path, config: &DocgenConfig) -> Result<Vec<DependencyEdge>> {
+    let packages = package_paths(root, config);
+
+    let mut edges: Vec<DependencyEdge> = Vec::new();
+    for (name, path) in &packages {
+        let mut add = |to: String, kind: DependencyKind| {
+            if &to != name && !edges.iter().any(|e| &e.from == name && e.to == to) {
+                edges.push(DependencyEdge {
+                    from: name.clone(),
+                    to,
+                    kind,
+                });
+            }
+        };
+
+        let pkg_json = path.join("package.json");
+        if pkg_json.exists() {
+            let content = std::fs::read_to_string(&pkg_json)
+                .with_context(|| format!("Failed to read {}", pkg_json.display()))?;
+            let pkg: serde_json::Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", pkg_json.display()))?;
+
+            for (field, kind) in DEPENDENCY_FIELDS {
+                let Some(deps) = pkg[field].as_object() else {
+                    continue;
+                };
+                for (dep, spec) in deps {
+                    let spec = spec.as_str().unwrap_or_default();
+                    let local = ["workspace:", "file:", "link:"]
+                        .iter()
+                        .any(|p| spec.starts_with(p));
+                    // Dev dependencies only count when linked to the workspace
+                    let internal = local
+                        || (kind != DependencyKind::Dev && packages.iter().any(|(n, _)| n == dep));
+                    if internal {
+                        add(dep.clone(), kind.clone());
+                    }
+                }
+            }
+        }
+
+        let manifest = path.join("Package.swift");
+        if manifest.exists() {
+            for dep in swift_dependencies(&manifest, &packages)? {
+                add(dep, DependencyKind::Runtime);
+            }
+        }
+    }
+
+    Ok(edges)
+}
+
+/// Configured packages of `Package.swift` `.package(...)` dependencies
+fn swift_dependencies(manifest: &Path, packages: &[(String, PathBuf)]) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(manifest)
+        .with_context(|| format!("Failed to read {}", manifest.display()))?;
+    let path_re =
+        regex::Regex::new(r#"\.package\(\s*(?:name:\s*"[^"]*"\s*,\s*)?path:\s*"([^"]+)""#)?;
+    let name_re = regex::Regex::new(r#"\.package\(\s*name:\s*"([^"]+)""#)?;
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+
+    let mut deps = Vec::new();
+    for cap in path_re.captures_iter(&content) {
+        let target = normalize(&dir.join(&cap[1]));
+        if let Some((name, _)) = packages.iter().find(|(_, p)| normalize(p) == target) {
+            deps.push(name.clone());
+        }
+    }
+    for cap in name_re.captures_iter(&content) {
+        if let Some((name, _)) = packages.iter().find(|(n, _)| n == &cap[1]) {
+            deps.push(name.clone());
+        }
+    }
+    Ok(deps)
+}
+
+/// Imports of other configured packages in each package's sources, with
+/// the imported names
+pub fn type_references(root: &Path, config: &DocgenConfig) -> Result<Vec<TypeReference>> {
+    let packages = package_paths(root, config);
+    let import_re = regex::Regex::new(
+        r#"(?s)\bimport\s+(?:type\s+)?(\{[^}]*\}|[\w$]+|\*\s+as\s+[\w$]+)\s+from\s+['"]([^'"]+)['"]"#,
+    )?;
+
+    let mut references: Vec<TypeReference> = Vec::new();
+    for (name, path) in &packages {
+        for file in typescript::source_files(path) {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let code = blank_comments(&content);
+
+            for cap in import_re.captures_iter(&code) {
+                let specifier = &cap[2];
+                let Some((target, _)) = packages.iter().find(|(n, _)| {
+                    n != name && (specifier == n || specifier.starts_with(&format!("{}/", n)))
+                }) else {
+                    continue;
+                };
+
+                let symbols: Vec<String> = cap[1]
+                    .trim_matches(|c| c == '{' || c == '}')
+                    .split(',')
+                    .map(|s| s.trim().trim_start_matches("type ").trim())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.split(" as ").next().unwrap_or(s).trim().to_string())
+                    .collect();
+
+                let reference = match references
+                    .iter_mut()
+                    .position(|r| &r.from == name && &r.to == target)
+                {
+                    Some(i) => &mut references[i],
+                    None => {
+                        references.push(TypeReference {
+                            from: name.clone(),
+                            to: target.clone(),
+                            symbols: Vec::new(),
+                        });
+                        references.last_mut().unwrap()
+                    }
+                };
+                for symbol in symbols {
+                    if !reference.symbols.contains(&symbol) {
+                        reference.symbols.push(symbol);
+                    }
+                }
+            }
+        }
+    }
+
+    for reference in &mut references {
+        reference.symbols.sort();
+    }
+    Ok(references)
+}
+
+/// Configured package names with absolute paths
+fn package_paths(root: &Path, config: &DocgenConfig) -> Vec<(String, PathBuf)> {
+    config
+        .packages
+        .iter()
+        .map(|pkg| {
+            let path = if pkg.path.is_absolute() {
+                pkg.path.clone()
+            } else {
+                root.join(&pkg.path)
+            };
+            (pkg.name.clone(), path)
+        })
+        .collect()
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
@@ -0,0 +1,88 @@
+//! Drizzle ORM schema extractor
+//!
+//! Reads `pgTable`/`mysqlTable`/`sqliteTable` definitions into
+//! storage-agnostic entity models, one per dialect.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{blank_comments, count_lines, matching_delimiter, object_entries, unquote};
+use crate::types::{EntityField, EntityModel};
+
+/// Extract Drizzle tables from every schema file under a directory
+pub fn extract_tables(dir: &Path) -> Result<Vec<EntityModel>> {
+    let mut files: Vec<_> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "ts"))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut tables = Vec::new();
+    for file in files {
+        tables.extend(extract_file(&file)?);
+    }
+    Ok(tables)
+}
+
+/// Extract Drizzle tables from a single file
+pub fn extract_file(path: &Path) -> Result<Vec<EntityModel>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    debug!("Extracting Drizzle tables from {}", path.display());
+
+    let table_re = regex::Regex::new(
+        r#"(?m)^export\s+const\s+(\w+)\s*=\s*(pg|mysql|sqlite)Table\(\s*['"](\w+)['"]\s*,\s*\{"#,
+    )?;
+    let column_re = regex::Regex::new(r"^(\w+)\s*\(")?;
+
+    let mut tables = Vec::new();
+
+    for cap in table_re.captures_iter(&code) {
+        let dialect = &cap[2];
+        let table = cap[3].to_string();
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let body = &code[open + 1..close];
+        let mut fields = Vec::new();
+        let mut search_from = 0;
+        for (key, value) in object_entries(body) {
+            let type_name = column_re
+                .captures(&value)
+                .map_or_else(|| value.clone(), |c| c[1].to_string());
+
+            // Columns are nullable unless chained with notNull()/primaryKey()
+            let nullable = !value.contains(".notNull()") && !value.contains(".primaryKey()");
+
+            let key_offset = body[search_from..]
+                .find(&format!("{}:", key))
+                .map_or(search_from, |i| search_from + i);
+            search_from = key_offset;
+
+            fields.push(EntityField {
+                name: unquote(&key).to_string(),
+                type_name,
+                nullable,
+                line: count_lines(&content[..open + 1 + key_offset]) + 1,
+            });
+        }
+
+        tables.push(EntityModel {
+            entity: table.clone(),
+            source: format!("drizzle-{}:{}", dialect, table),
+            file: path.to_path_buf(),
+            line: count_lines(&content[..cap.get(0).unwrap().start()]) + 1,
+            fields,
+        });
+    }
+
+    Ok(tables)
+}
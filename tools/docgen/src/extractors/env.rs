@@ -0,0 +1,233 @@
+//! Environment variable extractor
+//!
+//! Finds the variables a package reads through `process.env`,
+//! `import.meta.env` and SvelteKit's `$env/static|dynamic` modules, in its
+//! sources, doc comment examples and example apps.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{blank_comments, count_lines, SKIPPED_DIRS};
+use crate::types::{EnvUsage, EnvVar};
+
+/// Extract every environment variable read under a package directory
+pub fn extract_env_vars(pkg_path: &Path) -> Result<Vec<EnvVar>> {
+    let mut files: Vec<_> = WalkDir::new(pkg_path)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !(e.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            ["ts", "tsx", "js", "mjs", "cjs", "svelte"]
+                .iter()
+                .any(|ext| name.ends_with(&format!(".{}", ext)))
+                && !name.ends_with(".d.ts")
+                && !name.contains(".test.")
+                && !name.contains(".spec.")
+        })
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut vars: BTreeMap<String, EnvVar> = BTreeMap::new();
+    for file in files {
+        let example = file
+            .strip_prefix(pkg_path)
+            .is_ok_and(|p| p.components().any(|c| c.as_os_str() == "examples"));
+        for (name, access, usage, default) in extract_file(&file, example)? {
+            let var = vars.entry(name.clone()).or_insert_with(|| EnvVar {
+                name,
+                access: Vec::new(),
+                default: None,
+                usages: Vec::new(),
+            });
+            if !var.access.contains(&access) {
+                var.access.push(access);
+            }
+            if var.default.is_none() {
+                var.default = default;
+            }
+            if !var
+                .usages
+                .iter()
+                .any(|u| u.source_file == usage.source_file && u.line == usage.line)
+            {
+                var.usages.push(usage);
+            }
+        }
+    }
+
+    Ok(vars.into_values().collect())
+}
+
+type Read = (String, String, EnvUsage, Option<String>);
+
+/// Reads in a single file as `(name, access, usage, fallback)`
+fn extract_file(path: &Path, example: bool) -> Result<Vec<Read>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    debug!("Scanning {} for environment variables", path.display());
+
+    let mut reads = Vec::new();
+    let mut record = |name: &str, access: &str, start: usize, end: usize| {
+        // Comments were blanked in `code`, so a mismatch means a doc example
+        let in_comment = code[start..end] != content[start..end];
+        reads.push((
+            name.to_string(),
+            access.to_string(),
+            EnvUsage {
+                source_file: path.to_path_buf(),
+                line: count_lines(&content[..start]) + 1,
+                example: example || in_comment,
+                comment: adjacent_comment(&content, &code, start, end),
+            },
+            fallback(&content[end..]),
+        ));
+    };
+
+    let process_re = regex::Regex::new(
+        r#"process\.env(?:\.([A-Za-z_][A-Za-z0-9_]*)|\[\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]\s*\])"#,
+    )?;
+    for cap in process_re.captures_iter(&content) {
+        let m = cap.get(0).unwrap();
+        let name = cap.get(1).or(cap.get(2)).unwrap().as_str();
+        record(name, "process.env", m.start(), m.end());
+    }
+
+    let meta_re = regex::Regex::new(r"import\.meta\.env\.([A-Za-z_][A-Za-z0-9_]*)")?;
+    for cap in meta_re.captures_iter(&content) {
+        let m = cap.get(0).unwrap();
+        record(&cap[1], "import.meta.env", m.start(), m.end());
+    }
+
+    // `import { API_URL } from '$env/static/private'` names the variables;
+    // `import { env } from '$env/dynamic/public'` reads them as `env.X`
+    let sveltekit_re = regex::Regex::new(
+        r#"import\s*\{([^}]*)\}\s*from\s*['"]\$env/(static|dynamic)/(public|private)['"]"#,
+    )?;
+    for cap in sveltekit_re.captures_iter(&code) {
+        let m = cap.get(0).unwrap();
+        let access = format!("$env/{}/{}", &cap[2], &cap[3]);
+        for import in cap[1].split(',') {
+            let mut parts = import.split(" as ").map(str::trim);
+            let Some(imported) = parts.next().filter(|i| !i.is_empty()) else {
+                continue;
+            };
+            let local = parts.next().unwrap_or(imported);
+
+            if &cap[2] == "static" {
+                record(imported, &access, m.start(), m.end());
+                continue;
+            }
+            let usage_re = regex::Regex::new(&format!(
+                r"\b{}\.([A-Za-z_][A-Za-z0-9_]*)",
+                regex::escape(local)
+            ))?;
+            for usage in usage_re.captures_iter(&code) {
+                let u = usage.get(0).unwrap();
+                // `import.meta.env.X` is not the imported `env`
+                if code[..u.start()].ends_with('.') {
+                    continue;
+                }
+                record(&usage[1], &access, u.start(), u.end());
+            }
+        }
+    }
+
+    Ok(reads)
+}
+
+/// A `// comment` after the read on the same line, or the `//` lines
+/// directly above it
+fn adjacent_comment(content: &str, code: &str, start: usize, end: usize) -> Option<String> {
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[end..].find('\n').map_or(content.len(), |i| end + i);
+
+    if let Some(i) = line_comment_start(&content[end..line_end]) {
+        let text = content[end + i + 2..line_end].trim();
+        // In code the comment must be blanked; in doc examples everything is
+        let is_comment = code[end + i..end + i + 2] != content[end + i..end + i + 2];
+        if is_comment && !text.is_empty() {
+            return Some(text.to_string());
+        }
+    }
+
+    let mut above = Vec::new();
+    for line in content[..line_start].lines().rev() {
+        let Some(text) = line
+            .trim()
+            .trim_start_matches('*')
+            .trim()
+            .strip_prefix("//")
+        else {
+            break;
+        };
+        above.push(text.trim());
+    }
+    above.reverse();
+    let text = above.join(" ");
+    (!text.trim().is_empty()).then(|| text.trim().to_string())
+}
+
+/// Offset of a `//` that is not inside a string literal
+fn line_comment_start(text: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q && prev != '\\' => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+            None if c == '/' && prev == '/' => return Some(i - 1),
+            None => {}
+        }
+        prev = c;
+    }
+    None
+}
+
+/// The fallback in `process.env.PORT || 3000` or `?? '3000'`
+fn fallback(after: &str) -> Option<String> {
+    let rest = after.trim_start().trim_start_matches('!').trim_start();
+    let rest = rest
+        .strip_prefix("||")
+        .or_else(|| rest.strip_prefix("??"))?
+        .trim_start();
+
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut end = rest.len();
+    for (i, c) in rest.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => {
+                end = i;
+                break;
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' | ';' | '\n' if depth == 0 => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let value = rest[..end].trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
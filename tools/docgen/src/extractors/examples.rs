@@ -0,0 +1,260 @@
+//! Example region extractor
+//!
+//! Test suites hold the best usage examples, and CI keeps them working.
+//! Code between `// docgen:example <name>` and `// docgen:end` in any
+//! package file (tests included) becomes a named region that JSDoc pulls in
+//! with `@example {@include name}` and markdown with
+//! `<!-- docgen:example name -->`, which `generate` keeps as a
+//! `docgen:begin example=name` region in hand-written pages.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{count_lines, SKIPPED_DIRS};
+use crate::types::{DocgenConfig, ExampleRegion, ExtractedDocs, IssueSeverity, ValidationIssue};
+
+/// Extract the example regions of every configured package.
+///
+/// Names are global so a core package's docs can include an adapter's
+/// tests; a name defined twice is reported as a problem.
+pub fn collect(
+    root: &Path,
+    config: &DocgenConfig,
+) -> Result<(Vec<ExampleRegion>, Vec<ValidationIssue>)> {
+    let mut regions: Vec<ExampleRegion> = Vec::new();
+    let mut problems = Vec::new();
+
+    for pkg in &config.packages {
+        let pkg_path = if pkg.path.is_absolute() {
+            pkg.path.clone()
+        } else {
+            root.join(&pkg.path)
+        };
+        if !pkg_path.exists() {
+            continue;
+        }
+
+        let (found, issues) = extract_regions(&pkg_path)?;
+        problems.extend(issues);
+        for region in found {
+            if let Some(first) = find(&regions, &region.name) {
+                problems.push(ValidationIssue {
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "Example region `{}` is already defined in {}:{}",
+                        region.name,
+                        first.source_file.display(),
+                        first.line
+                    ),
+                    file: Some(region.source_file.clone()),
+                    line: Some(region.line),
+                    suggestion: Some("Give each example region a unique name".to_string()),
+                });
+                continue;
+            }
+            regions.push(region);
+        }
+    }
+
+    Ok((regions, problems))
+}
+
+/// Extract every example region under a package directory.
+///
+/// Also returns the marker problems found (unterminated regions, stray
+/// `docgen:end` markers).
+pub fn extract_regions(pkg_path: &Path) -> Result<(Vec<ExampleRegion>, Vec<ValidationIssue>)> {
+    let mut files: Vec<_> = WalkDir::new(pkg_path)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !(e.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| language(e.path()).is_some())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut regions = Vec::new();
+    let mut problems = Vec::new();
+    for file in files {
+        extract_file(&file, &mut regions, &mut problems)?;
+    }
+
+    Ok((regions, problems))
+}
+
+fn extract_file(
+    path: &Path,
+    regions: &mut Vec<ExampleRegion>,
+    problems: &mut Vec<ValidationIssue>,
+) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if !content.contains("docgen:") {
+        return Ok(());
+    }
+
+    let start_re = regex::Regex::new(r"^\s*//\s*docgen:example\s+([\w./-]+)\s*$")?;
+    let end_re = regex::Regex::new(r"^\s*//\s*docgen:end\s*$")?;
+    let problem = |message: String, line: usize| ValidationIssue {
+        severity: IssueSeverity::Error,
+        message,
+        file: Some(path.to_path_buf()),
+        line: Some(line),
+        suggestion: None,
+    };
+
+    // Open regions as (name, marker line, body lines); regions may nest
+    let mut open: Vec<(String, usize, Vec<&str>)> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        if let Some(cap) = start_re.captures(line) {
+            open.push((cap[1].to_string(), number, Vec::new()));
+        } else if end_re.is_match(line) {
+            let Some((name, start, body)) = open.pop() else {
+                problems.push(problem(
+                    "`docgen:end` without a matching `docgen:example`".to_string(),
+                    number,
+                ));
+                continue;
+            };
+            debug!("Found example region {} in {}", name, path.display());
+            regions.push(ExampleRegion {
+                name,
+                language: language(path).unwrap_or("typescript").to_string(),
+                code: dedent(&body),
+                source_file: path.to_path_buf(),
+                line: start,
+            });
+        } else {
+            for (_, _, body) in &mut open {
+                body.push(line);
+            }
+        }
+    }
+
+    for (name, start, _) in open {
+        problems.push(problem(
+            format!(
+                "Example region `{}` is never closed with `docgen:end`",
+                name
+            ),
+            start,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Replace `{@include name}` in JSDoc examples and
+/// `<!-- docgen:example name -->` in the README with the regions' code.
+///
+/// Unknown names are left in place for `docgen validate` to report.
+pub fn apply(docs: &mut ExtractedDocs, regions: &[ExampleRegion]) {
+    for export in docs
+        .package
+        .exports
+        .iter_mut()
+        .chain(docs.files.values_mut().flatten())
+    {
+        for example in &mut export.examples {
+            *example = expand_includes(example, regions);
+        }
+    }
+
+    if let Some(readme) = &mut docs.readme {
+        *readme = expand_markers(readme, regions);
+    }
+}
+
+/// `{@include name}` -> the region's code
+pub fn expand_includes(text: &str, regions: &[ExampleRegion]) -> String {
+    let include_re =
+        regex::Regex::new(r"\{@include\s+([\w./-]+)\s*\}").expect("valid include regex");
+    include_re
+        .replace_all(text, |cap: &regex::Captures| {
+            find(regions, &cap[1]).map_or_else(|| cap[0].to_string(), |r| r.code.clone())
+        })
+        .into_owned()
+}
+
+/// `<!-- docgen:example name -->` -> a fenced code block with the region
+pub fn expand_markers(markdown: &str, regions: &[ExampleRegion]) -> String {
+    let marker_re =
+        regex::Regex::new(r"<!--\s*docgen:example\s+([\w./-]+)\s*-->").expect("valid marker regex");
+    marker_re
+        .replace_all(markdown, |cap: &regex::Captures| {
+            fenced(regions, &cap[1]).unwrap_or_else(|| cap[0].to_string())
+        })
+        .into_owned()
+}
+
+/// The region `name` as a fenced code block
+pub fn fenced(regions: &[ExampleRegion], name: &str) -> Option<String> {
+    find(regions, name).map(|r| format!("```{}\n{}\n```", r.language, r.code))
+}
+
+/// Region names referenced by `{@include}` or markers in some text, with
+/// their line numbers
+pub fn referenced_names(text: &str) -> Vec<(String, usize)> {
+    let reference_re = regex::Regex::new(
+        r"\{@include\s+([\w./-]+)\s*\}|<!--\s*docgen:example\s+([\w./-]+)\s*-->|<!--\s*docgen:begin\b[^>]*?\bexample=([\w./-]+)",
+    )
+    .expect("valid reference regex");
+    reference_re
+        .captures_iter(text)
+        .map(|cap| {
+            let name = cap.get(1).or(cap.get(2)).or(cap.get(3)).unwrap();
+            (
+                name.as_str().to_string(),
+                count_lines(&text[..name.start()]) + 1,
+            )
+        })
+        .collect()
+}
+
+fn find<'a>(regions: &'a [ExampleRegion], name: &str) -> Option<&'a ExampleRegion> {
+    regions.iter().find(|r| r.name == name)
+}
+
+fn language(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_string_lossy();
+    if name.ends_with(".d.ts") {
+        return None;
+    }
+    match path.extension()?.to_str()? {
+        "ts" | "tsx" | "mts" => Some("typescript"),
+        "js" | "mjs" | "cjs" => Some("javascript"),
+        "svelte" => Some("svelte"),
+        "swift" => Some("swift"),
+        _ => None,
+    }
+}
+
+/// Strip the indentation shared by all non-blank lines
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            if l.len() >= indent {
+                &l[indent..]
+            } else {
+                l.trim_start()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
@@ -0,0 +1,713 @@
+//! Fastify route extractor
+//!
+//! Recognizes `fastify.get('/path', { ...options }, handler)` style route
+//! registrations and derives an HTTP-level description of each endpoint:
+//! parameters, validation schemas, auth, rate limits and responses. Also
+//! finds the decorators and lifecycle hooks plugins add to the instance.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{
+    blank_comments, count_lines, extract_jsdoc, matching_delimiter, object_entries, parse_members,
+    split_top_level, unquote,
+};
+use crate::types::{
+    Decorator, LifecycleHook, PluginSurface, Property, RateLimit, Route, RouteResponse,
+};
+
+/// `@fastify/sensible` reply helpers and the status they send
+const REPLY_HELPERS: [(&str, u16); 9] = [
+    ("badRequest", 400),
+    ("unauthorized", 401),
+    ("forbidden", 403),
+    ("notFound", 404),
+    ("conflict", 409),
+    ("tooManyRequests", 429),
+    ("internalServerError", 500),
+    ("notImplemented", 501),
+    ("serviceUnavailable", 503),
+];
+
+/// Messages and `(field, expression)` body entries collected per status
+type ResponseParts = (Vec<String>, Vec<(String, String)>);
+
+/// Extract routes from a package's source files.
+///
+/// Route prefixes are resolved by looking for `register(factory(...), { prefix })`
+/// calls anywhere in the package, including README and example snippets.
+pub fn extract_routes(pkg_path: &Path, files: &[PathBuf]) -> Result<Vec<Route>> {
+    let mut found = Vec::new();
+    for file in files {
+        found.extend(extract_file(file)?);
+    }
+
+    if found.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut prefixes: HashMap<String, Option<String>> = HashMap::new();
+    let mut routes = Vec::new();
+    for (factory, mut route) in found {
+        if let Some(factory) = factory {
+            let prefix = prefixes
+                .entry(factory.clone())
+                .or_insert_with(|| find_prefix(pkg_path, &factory));
+            route.prefix = prefix.clone();
+        }
+        routes.push(route);
+    }
+
+    Ok(routes)
+}
+
+/// Extract routes from a single file, paired with the exported function
+/// that registers them
+fn extract_file(path: &Path) -> Result<Vec<(Option<String>, Route)>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    let route_re = regex::Regex::new(
+        r#"\b(?:fastify|app|server|instance)\.(get|post|put|patch|delete|head|options)\s*(<)?"#,
+    )?;
+
+    let factories = exported_functions(&code)?;
+    let constants = const_objects(&code)?;
+    let cookies = cookie_names(&code)?;
+
+    let mut routes = Vec::new();
+
+    for cap in route_re.captures_iter(&code) {
+        let start = cap.get(0).unwrap().start();
+        let mut cursor = cap.get(0).unwrap().end();
+
+        // Optional route generic: `.post<{ Body: ... }>(`
+        let mut generic = None;
+        if cap.get(2).is_some() {
+            let Some(generic_end) = matching_delimiter(&code, cursor - 1) else {
+                continue;
+            };
+            generic = Some(&code[cursor..generic_end]);
+            cursor = generic_end + 1;
+        }
+
+        let rest = &code[cursor..];
+        if !rest.trim_start().starts_with('(') {
+            continue;
+        }
+        let open = cursor + (rest.len() - rest.trim_start().len());
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let args = split_top_level(&code[open + 1..close], ',');
+        let Some(path_arg) = args.first().map(|a| a.trim()) else {
+            continue;
+        };
+        if !(path_arg.starts_with('\'') || path_arg.starts_with('"')) {
+            continue;
+        }
+        let route_path = unquote(path_arg).to_string();
+
+        let options = if args.len() > 2 {
+            args[1].trim().to_string()
+        } else {
+            String::new()
+        };
+        let handler = args.last().map(|a| a.as_str()).unwrap_or("");
+
+        debug!("Found route {} {}", cap[1].to_uppercase(), route_path);
+
+        let mut route = Route {
+            method: cap[1].to_uppercase(),
+            path: route_path.clone(),
+            prefix: None,
+            description: route_description(&content, start, &cap[1], &route_path),
+            source_file: path.to_path_buf(),
+            line: count_lines(&content[..start]) + 1,
+            path_params: path_params(&route_path),
+            body_type: None,
+            body_schema: None,
+            params_schema: None,
+            query_schema: None,
+            auth_required: false,
+            rate_limit: None,
+            cookies_read: Vec::new(),
+            cookies_set: Vec::new(),
+            responses: Vec::new(),
+        };
+
+        if let Some(generic) = generic {
+            let generic = generic.trim();
+            if let Some(inner) = generic.strip_prefix('{').and_then(|g| g.strip_suffix('}')) {
+                for member in parse_members(inner, 0) {
+                    if member.name == "Body" {
+                        route.body_type = Some(member.type_annotation);
+                    }
+                }
+            }
+        }
+
+        apply_options(&mut route, &options, &constants);
+        apply_handler(&mut route, handler);
+        apply_cookies(&mut route, handler, &cookies);
+
+        let factory = factories
+            .iter()
+            .find(|(_, range)| range.0 < start && start < range.1)
+            .map(|(name, _)| name.clone());
+
+        routes.push((factory, route));
+    }
+
+    Ok(routes)
+}
+
+/// Read `preHandler`, `config.rateLimit` and `schema` route options
+fn apply_options(route: &mut Route, options: &str, constants: &HashMap<String, String>) {
+    let Some(inner) = options.strip_prefix('{').and_then(|o| o.strip_suffix('}')) else {
+        return;
+    };
+
+    for (key, value) in object_entries(inner) {
+        match key.as_str() {
+            "preHandler" | "onRequest" | "preValidation" if value.contains("authenticate") => {
+                route.auth_required = true;
+            }
+            "config" => {
+                let Some(config) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) else {
+                    continue;
+                };
+                for (config_key, config_value) in object_entries(config) {
+                    if config_key == "rateLimit" {
+                        route.rate_limit = resolve_rate_limit(&config_value, constants);
+                    }
+                }
+            }
+            "schema" => {
+                let Some(schema) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) else {
+                    continue;
+                };
+                for (schema_key, schema_value) in object_entries(schema) {
+                    match schema_key.as_str() {
+                        "body" => route.body_schema = Some(schema_value),
+                        "params" => route.params_schema = Some(schema_value),
+                        "querystring" | "query" => route.query_schema = Some(schema_value),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Resolve `{ max, timeWindow }` inline or through a constant like
+/// `rates.apple` -> `DEFAULT_RATE_LIMITS.apple`
+fn resolve_rate_limit(value: &str, constants: &HashMap<String, String>) -> Option<RateLimit> {
+    let value = value.trim();
+
+    let literal = if value.starts_with('{') {
+        Some(value.to_string())
+    } else {
+        let member = value.rsplit('.').next()?;
+        let mut names: Vec<_> = constants.keys().collect();
+        names.sort();
+        names.into_iter().find_map(|name| {
+            object_entries(&constants[name])
+                .into_iter()
+                .find(|(k, v)| k == member && v.contains("max"))
+                .map(|(_, v)| v)
+        })
+    }?;
+
+    let inner = literal.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let entries: HashMap<_, _> = object_entries(inner).into_iter().collect();
+
+    Some(RateLimit {
+        max: entries.get("max")?.clone(),
+        time_window: entries
+            .get("timeWindow")
+            .map_or_else(|| "-".to_string(), |w| unquote(w).to_string()),
+    })
+}
+
+/// Scan the handler for validated inputs and the responses it sends
+fn apply_handler(route: &mut Route, handler: &str) {
+    let parse_re = regex::Regex::new(
+        r"(\w+)\.(?:safeParse|parse|safeParseAsync|parseAsync)\(\s*request\.(body|params|query)\s*\)",
+    )
+    .expect("valid parse regex");
+
+    for cap in parse_re.captures_iter(handler) {
+        let schema = Some(cap[1].to_string());
+        match &cap[2] {
+            "body" => route.body_schema = schema,
+            "params" => route.params_schema = schema,
+            _ => route.query_schema = schema,
+        }
+    }
+
+    let mut responses: BTreeMap<u16, ResponseParts> = BTreeMap::new();
+
+    // reply.badRequest('...') and friends
+    let helper_re = regex::Regex::new(r"reply\.(\w+)\(").expect("valid helper regex");
+    for cap in helper_re.captures_iter(handler) {
+        let Some(&(_, status)) = REPLY_HELPERS.iter().find(|(name, _)| *name == &cap[1]) else {
+            continue;
+        };
+        let open = cap.get(0).unwrap().end() - 1;
+        let args = matching_delimiter(handler, open)
+            .map(|close| &handler[open + 1..close])
+            .unwrap_or("");
+        let message = match args.trim() {
+            "" => None,
+            arg if arg.starts_with('\'') || arg.starts_with('"') => Some(unquote(arg).to_string()),
+            _ => Some("Validation error".to_string()),
+        };
+
+        let entry = responses.entry(status).or_default();
+        if let Some(message) = message {
+            if !entry.0.contains(&message) {
+                entry.0.push(message);
+            }
+        }
+    }
+
+    // reply.code(423).send({ error: '...', ... })
+    let code_re = regex::Regex::new(r"reply\.(?:code|status)\((\d{3})\)\s*\.send\(\s*\{")
+        .expect("valid code regex");
+    for cap in code_re.captures_iter(handler) {
+        let Ok(status) = cap[1].parse::<u16>() else {
+            continue;
+        };
+        let open = cap.get(0).unwrap().end() - 1;
+        let entries = matching_delimiter(handler, open)
+            .map(|close| object_entries(&handler[open + 1..close]))
+            .unwrap_or_default();
+
+        let entry = responses.entry(status).or_default();
+        for (key, value) in entries {
+            if key == "error" && !entry.0.contains(&unquote(&value).to_string()) {
+                entry.0.push(unquote(&value).to_string());
+            }
+            if !entry.1.iter().any(|(k, _)| k == &key) {
+                let value = resolve_local(handler, &key, value);
+                entry.1.push((key, value));
+            }
+        }
+    }
+
+    // Plain `return { ... }` is the success payload
+    let return_re = regex::Regex::new(r"\breturn\s*\{").expect("valid return regex");
+    for cap in return_re.captures_iter(handler) {
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(close) = matching_delimiter(handler, open) else {
+            continue;
+        };
+        let entry = responses.entry(200).or_default();
+        for (key, value) in object_entries(&handler[open + 1..close]) {
+            if !entry.1.iter().any(|(k, _)| k == &key) {
+                let value = resolve_local(handler, &key, value);
+                entry.1.push((key, value));
+            }
+        }
+    }
+
+    if route.auth_required {
+        let entry = responses.entry(401).or_default();
+        entry
+            .0
+            .insert(0, "Missing or invalid access token".to_string());
+    }
+    if route.rate_limit.is_some() {
+        let entry = responses.entry(429).or_default();
+        entry.0.push("Rate limit exceeded".to_string());
+    }
+
+    route.responses = responses
+        .into_iter()
+        .map(|(status, (messages, fields))| RouteResponse {
+            status,
+            description: if messages.is_empty() && status < 300 {
+                "Success".to_string()
+            } else {
+                messages.join("; ")
+            },
+            fields,
+        })
+        .collect();
+}
+
+/// Expand a shorthand property (`{ sessions }`) to the initializer of the
+/// local it refers to, so the value can be typed later
+fn resolve_local(handler: &str, key: &str, value: String) -> String {
+    if value != key {
+        return value;
+    }
+    let Ok(local_re) = regex::Regex::new(&format!(
+        r"\b(?:const|let)\s+{}\s*(?::[^=]+)?=\s*",
+        regex::escape(key)
+    )) else {
+        return value;
+    };
+    match local_re.find(handler) {
+        Some(m) => split_top_level(&handler[m.end()..], ';')
+            .into_iter()
+            .next()
+            .map(|init| init.trim().to_string())
+            .filter(|init| !init.is_empty())
+            .unwrap_or(value),
+        None => value,
+    }
+}
+
+/// Record cookies read through `request.cookies[...]` and set through
+/// `reply.setCookie(...)`
+fn apply_cookies(route: &mut Route, handler: &str, cookies: &HashMap<String, String>) {
+    let read_re = regex::Regex::new(r#"request\.cookies\b[^;\n]*?\[\s*([\w'"]+)\s*\]"#)
+        .expect("valid cookie read regex");
+    let set_re =
+        regex::Regex::new(r#"reply\.setCookie\(\s*([\w'"]+)"#).expect("valid setCookie regex");
+
+    let resolve = |name: &str| {
+        cookies
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| unquote(name).to_string())
+    };
+
+    for cap in read_re.captures_iter(handler) {
+        let name = resolve(&cap[1]);
+        if !route.cookies_read.contains(&name) {
+            route.cookies_read.push(name);
+        }
+    }
+    for cap in set_re.captures_iter(handler) {
+        let name = resolve(&cap[1]);
+        if !route.cookies_set.contains(&name) {
+            route.cookies_set.push(name);
+        }
+    }
+}
+
+/// Properties added to `FastifyRequest` by `declare module 'fastify'`
+pub fn extract_request_decorations(files: &[PathBuf]) -> Result<Vec<Property>> {
+    let mut properties = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        properties.extend(augmented_members(&content, "FastifyRequest")?);
+    }
+    Ok(properties)
+}
+
+/// Members of a Fastify interface (`FastifyRequest`, ...) augmented in a file
+fn augmented_members(content: &str, interface: &str) -> Result<Vec<Property>> {
+    let interface_re = regex::Regex::new(&format!(
+        r"\binterface\s+{}\s*\{{",
+        regex::escape(interface)
+    ))?;
+    let code = blank_comments(content);
+
+    let mut properties = Vec::new();
+    for m in interface_re.find_iter(&code) {
+        let open = m.end() - 1;
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+        let first_line = count_lines(&content[..open]) + 1;
+        properties.extend(parse_members(&content[open + 1..close], first_line));
+    }
+    Ok(properties)
+}
+
+/// Extract what Fastify plugins add to the instance they are registered on:
+/// `decorate*` calls, `addHook` registrations and `fastify-plugin` metadata.
+///
+/// Decorator types come from the `FastifyInstance`, `FastifyRequest` and
+/// `FastifyReply` augmentations anywhere in `files`.
+pub fn extract_plugins(files: &[PathBuf]) -> Result<Vec<PluginSurface>> {
+    let decorate_re =
+        regex::Regex::new(r#"\.decorate(Request|Reply)?\(\s*['"]([A-Za-z_$][\w$]*)['"]\s*,"#)?;
+    let hook_re = regex::Regex::new(r#"\.addHook\(\s*['"](\w+)['"]\s*,"#)?;
+    let fp_re = regex::Regex::new(r"\b(?:fp|fastifyPlugin)\(\s*([A-Za-z_$][\w$]*)\s*,\s*\{")?;
+
+    let mut contents = Vec::new();
+    let mut augmented: Vec<(&str, Property)> = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        for (target, interface) in [
+            ("instance", "FastifyInstance"),
+            ("request", "FastifyRequest"),
+            ("reply", "FastifyReply"),
+        ] {
+            for member in augmented_members(&content, interface)? {
+                augmented.push((target, member));
+            }
+        }
+        contents.push((file, content));
+    }
+
+    let mut plugins = Vec::new();
+    for (file, content) in &contents {
+        let code = blank_comments(content);
+        let line_of = |offset: usize| count_lines(&content[..offset]) + 1;
+        let line_start = |offset: usize| content[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+        let mut decorators = Vec::new();
+        for cap in decorate_re.captures_iter(&code) {
+            let start = cap.get(0).unwrap().start();
+            let target = match cap.get(1).map(|t| t.as_str()) {
+                Some("Request") => "request",
+                Some("Reply") => "reply",
+                _ => "instance",
+            };
+            let declared = augmented
+                .iter()
+                .find(|(t, m)| *t == target && m.name == cap[2])
+                .map(|(_, m)| m);
+            decorators.push(Decorator {
+                target: target.to_string(),
+                name: cap[2].to_string(),
+                type_annotation: declared.map(|m| m.type_annotation.clone()),
+                description: declared
+                    .and_then(|m| m.description.clone())
+                    .or_else(|| comment_above(content, line_start(start))),
+                line: line_of(start),
+            });
+        }
+
+        let mut hooks = Vec::new();
+        for cap in hook_re.captures_iter(&code) {
+            let m = cap.get(0).unwrap();
+            let args_end = matching_delimiter(&code, m.start() + ".addHook".len());
+            let handler = args_end
+                .map(|end| code[m.end()..end].trim())
+                .filter(|h| h.chars().all(|c| c.is_alphanumeric() || "_$.".contains(c)))
+                .map(|h| h.to_string());
+            let line_start = line_start(m.start());
+            hooks.push(LifecycleHook {
+                name: cap[1].to_string(),
+                handler,
+                description: extract_jsdoc(content, line_start)
+                    .description
+                    .or_else(|| comment_above(content, line_start)),
+                line: line_of(m.start()),
+            });
+        }
+
+        let mut metadata = None;
+        if let Some(cap) = fp_re.captures(&code) {
+            let open = cap.get(0).unwrap().end() - 1;
+            if let Some(close) = matching_delimiter(&code, open) {
+                metadata = Some((
+                    object_entries(&code[open + 1..close]),
+                    line_of(cap.get(0).unwrap().start()),
+                ));
+            }
+        }
+
+        if decorators.is_empty() && hooks.is_empty() && metadata.is_none() {
+            continue;
+        }
+
+        let (entries, line) = metadata.unwrap_or_default();
+        let entry = |key: &str| {
+            entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.trim().to_string())
+        };
+        let dependencies = entry("dependencies")
+            .and_then(|d| {
+                d.strip_prefix('[')
+                    .and_then(|d| d.strip_suffix(']'))
+                    .map(str::to_string)
+            })
+            .map(|d| {
+                split_top_level(&d, ',')
+                    .iter()
+                    .map(|dep| unquote(dep).to_string())
+                    .filter(|dep| !dep.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        debug!("Found plugin surface in {}", file.display());
+        plugins.push(PluginSurface {
+            name: entry("name").map(|n| unquote(&n).to_string()),
+            dependencies,
+            fastify_version: entry("fastify").map(|v| unquote(&v).to_string()),
+            decorators,
+            hooks,
+            source_file: file.to_path_buf(),
+            line: line.max(1),
+        });
+    }
+
+    Ok(plugins)
+}
+
+/// Summary from a JSDoc block or the banner comment above a route,
+/// with a leading "GET /path - " removed
+fn route_description(content: &str, start: usize, method: &str, path: &str) -> Option<String> {
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    if let Some(desc) = extract_jsdoc(content, line_start).description {
+        return Some(desc);
+    }
+
+    let text = comment_above(content, line_start)?;
+    let banner = format!("{} {}", method.to_uppercase(), path);
+    let text = text
+        .strip_prefix(&banner)
+        .map(|t| t.trim_start_matches([' ', '-', ':']).to_string())
+        .unwrap_or(text);
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// The `//` comment lines directly above `line_start`, skipping `====`
+/// separator lines
+fn comment_above(content: &str, line_start: usize) -> Option<String> {
+    let mut lines = Vec::new();
+    for line in content[..line_start].lines().rev() {
+        let line = line.trim();
+        // Banner comments may be separated from the call by blank lines
+        if line.is_empty() && lines.is_empty() {
+            continue;
+        }
+        let Some(text) = line.strip_prefix("//") else {
+            break;
+        };
+        let text = text.trim();
+        if text.chars().all(|c| c == '=' || c == '-' || c == '*') {
+            continue;
+        }
+        lines.push(text.to_string());
+    }
+    lines.reverse();
+
+    let text = lines.join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+fn path_params(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|p| p.trim_end_matches('?').to_string())
+        .collect()
+}
+
+/// Exported functions and the byte range of their bodies
+fn exported_functions(code: &str) -> Result<Vec<(String, (usize, usize))>> {
+    let fn_re = regex::Regex::new(
+        r"(?m)^export\s+(?:default\s+)?(?:async\s+)?function\s+(\w+)\s*(?:<[^>]*>)?\s*\(",
+    )?;
+
+    let mut functions = Vec::new();
+    for cap in fn_re.captures_iter(code) {
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(params_end) = matching_delimiter(code, open) else {
+            continue;
+        };
+        let Some(body) = code[params_end..].find('{').map(|i| params_end + i) else {
+            continue;
+        };
+        if let Some(body_end) = matching_delimiter(code, body) {
+            functions.push((cap[1].to_string(), (body, body_end)));
+        }
+    }
+    Ok(functions)
+}
+
+/// Top-level `const NAME = { ... }` objects, by name
+fn const_objects(code: &str) -> Result<HashMap<String, String>> {
+    let const_re = regex::Regex::new(r"(?m)^(?:export\s+)?const\s+(\w+)(?:\s*:[^=]+)?=\s*\{")?;
+
+    let mut objects = HashMap::new();
+    for cap in const_re.captures_iter(code) {
+        let open = cap.get(0).unwrap().end() - 1;
+        if let Some(close) = matching_delimiter(code, open) {
+            objects.insert(cap[1].to_string(), code[open + 1..close].to_string());
+        }
+    }
+    Ok(objects)
+}
+
+/// Cookie name constants, with `${prefix}` template parts replaced by the
+/// default the variable is destructured with (`cookiePrefix = 'auth'`)
+fn cookie_names(code: &str) -> Result<HashMap<String, String>> {
+    let const_re =
+        regex::Regex::new(r#"\bconst\s+(\w*COOKIE\w*)\s*=\s*(`[^`]*`|'[^']*'|"[^"]*")"#)?;
+    let var_re = regex::Regex::new(r"\$\{(\w+)\}")?;
+
+    let mut names = HashMap::new();
+    for cap in const_re.captures_iter(code) {
+        let template = unquote(&cap[2]).to_string();
+        let name = var_re.replace_all(&template, |var: &regex::Captures| {
+            let default_re = regex::Regex::new(&format!(
+                r#"\b{}\s*=\s*['"]([^'"]*)['"]"#,
+                regex::escape(&var[1])
+            ))
+            .expect("valid default regex");
+            default_re
+                .captures(code)
+                .map_or_else(|| var[0].to_string(), |d| d[1].to_string())
+        });
+        names.insert(cap[1].to_string(), name.into_owned());
+    }
+    Ok(names)
+}
+
+/// Find the prefix a routes factory is registered under
+fn find_prefix(pkg_path: &Path, factory: &str) -> Option<String> {
+    let register_re =
+        regex::Regex::new(&format!(r"\.register\(\s*{}\b", regex::escape(factory))).ok()?;
+    let prefix_re = regex::Regex::new(r#"prefix:\s*['"]([^'"]+)['"]"#).ok()?;
+
+    let mut files: Vec<_> = WalkDir::new(pkg_path)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != "node_modules" && e.file_name() != "dist")
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .is_some_and(|ext| ext == "ts" || ext == "js" || ext == "md")
+        })
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    // Most common prefix wins; ties go to the first seen
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        for m in register_re.find_iter(&content) {
+            let open = m.start() + ".register".len();
+            let Some(close) = matching_delimiter(&content, open) else {
+                continue;
+            };
+            if let Some(cap) = prefix_re.captures(&content[open..close]) {
+                match counts.iter_mut().find(|(p, _)| p == &cap[1]) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((cap[1].to_string(), 1)),
+                }
+            }
+        }
+    }
+
+    let max = counts.iter().map(|(_, n)| *n).max()?;
+    counts.into_iter().find(|(_, n)| *n == max).map(|(p, _)| p)
+}
@@ -0,0 +1,97 @@
+//! TypeScript API client extractor
+//!
+//! Finds calls to fetch wrappers such as `apiFetch('/api/v1/auth/me', {...})`
+//! in web clients, with the method and JSON body keys they send.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use super::typescript::{
+    blank_comments, count_lines, matching_delimiter, object_entries, split_top_level, unquote,
+};
+use crate::types::ClientEndpoint;
+
+/// Extract API calls from TypeScript sources.
+///
+/// Only wrappers whose name mentions the API (`apiFetch`, `fetchApi`, ...)
+/// are considered: plain `fetch('/auth/logout')` in a SvelteKit app usually
+/// targets the app's own endpoints rather than the auth server.
+pub fn extract_endpoints(files: &[PathBuf], client: &str) -> Result<Vec<ClientEndpoint>> {
+    let call_re = regex::Regex::new(r#"\b(\w*(?:api|Api)\w*)\s*(?:<[^>()]*>)?\(\s*['"`]/"#)?;
+
+    let mut endpoints = Vec::new();
+    for file in files {
+        endpoints.extend(extract_file(&call_re, file, client)?);
+    }
+    Ok(endpoints)
+}
+
+fn extract_file(call_re: &regex::Regex, path: &Path, client: &str) -> Result<Vec<ClientEndpoint>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    let mut endpoints = Vec::new();
+    for cap in call_re.captures_iter(&code) {
+        let start = cap.get(0).unwrap().start();
+        let Some(open) = code[start..].find('(').map(|i| start + i) else {
+            continue;
+        };
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let args = split_top_level(&code[open + 1..close], ',');
+        let Some(path_arg) = args.first() else {
+            continue;
+        };
+        let url = unquote(path_arg).to_string();
+
+        let mut method = "GET".to_string();
+        let mut body_fields = Some(Vec::new());
+        if let Some(options) = args
+            .get(1)
+            .map(|o| o.trim())
+            .and_then(|o| o.strip_prefix('{'))
+            .and_then(|o| o.strip_suffix('}'))
+        {
+            for (key, value) in object_entries(options) {
+                match key.as_str() {
+                    "method" => method = unquote(&value).to_uppercase(),
+                    "body" => body_fields = json_body_keys(&value),
+                    _ => {}
+                }
+            }
+        }
+
+        debug!("Found API call {} {} in {}", method, url, path.display());
+
+        endpoints.push(ClientEndpoint {
+            client: client.to_string(),
+            name: Some(cap[1].to_string()),
+            method: Some(method),
+            path: url,
+            body_fields,
+            source_file: path.to_path_buf(),
+            line: count_lines(&content[..start]) + 1,
+        });
+    }
+
+    Ok(endpoints)
+}
+
+/// Keys of `JSON.stringify({ code, state })`; `None` when not a literal
+fn json_body_keys(value: &str) -> Option<Vec<String>> {
+    let inner = value
+        .trim()
+        .strip_prefix("JSON.stringify(")?
+        .strip_suffix(')')?;
+    let object = inner.trim().strip_prefix('{')?.strip_suffix('}')?;
+    Some(
+        object_entries(object)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect(),
+    )
+}
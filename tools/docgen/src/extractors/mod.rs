@@ -1,3 +1,17 @@
 //! Source code extractors for documentation generation
 
+pub mod changelog;
+pub mod deps;
+pub mod drizzle;
+pub mod env;
+pub mod examples;
+pub mod fastify;
+pub mod fetch;
+pub mod mongoose;
+pub mod options;
+pub mod security;
+pub mod sveltekit;
+pub mod sql;
+pub mod swift;
 pub mod typescript;
+pub mod zod;
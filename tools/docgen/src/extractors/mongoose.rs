@@ -0,0 +1,329 @@
+//! Mongoose schema extractor
+//!
+//! Extracts collection definitions from `new Schema(...)` calls, including
+//! field options, schema-level indexes and TTL settings, so the MongoDB
+//! adapter docs can describe what actually ends up in the database.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{
+    blank_comments, count_lines, extract_jsdoc, matching_delimiter, object_entries, unquote,
+};
+use crate::types::{EntityField, EntityModel, MongooseModel, SchemaField, SchemaIndex};
+
+/// Extract all Mongoose models defined under a directory
+pub fn extract_models(dir: &Path) -> Result<Vec<MongooseModel>> {
+    let mut models = Vec::new();
+
+    let mut files: Vec<_> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "ts"))
+        .filter(|e| !e.path().to_string_lossy().contains(".test."))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    for file in files {
+        models.extend(extract_file(&file)?);
+    }
+
+    Ok(models)
+}
+
+/// Extract Mongoose models from a single file
+pub fn extract_file(path: &Path) -> Result<Vec<MongooseModel>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    debug!("Extracting Mongoose schemas from {}", path.display());
+
+    // Schema variable -> model name, from `connection.model<T>('User', userSchema)`
+    let model_re = regex::Regex::new(r#"\.model(?:<[^>]+>)?\(\s*['"](\w+)['"]\s*,\s*(\w+)"#)?;
+    let model_names: HashMap<String, String> = model_re
+        .captures_iter(&code)
+        .map(|cap| (cap[2].to_string(), cap[1].to_string()))
+        .collect();
+
+    let schema_re =
+        regex::Regex::new(r"(?m)^(?:export\s+)?const\s+(\w+)\s*=\s*new\s+(?:mongoose\.)?Schema\b")?;
+
+    let mut models = Vec::new();
+
+    for cap in schema_re.captures_iter(&code) {
+        let schema = cap[1].to_string();
+        let start = cap.get(0).unwrap().start();
+
+        // Locate `(` then the definition object and the optional options object
+        let Some(paren) = code[start..].find('(').map(|i| start + i) else {
+            continue;
+        };
+        let Some(paren_end) = matching_delimiter(&code, paren) else {
+            continue;
+        };
+        let args = &code[paren + 1..paren_end];
+        let Some(def_open) = args.find('{') else {
+            continue;
+        };
+        let Some(def_close) = matching_delimiter(args, def_open) else {
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        collect_fields(&args[def_open + 1..def_close], "", &mut fields);
+
+        let rest = &args[def_close + 1..];
+        let options = rest
+            .find('{')
+            .and_then(|open| matching_delimiter(rest, open).map(|close| &rest[open + 1..close]))
+            .map(object_entries)
+            .unwrap_or_default();
+
+        let mut collection = None;
+        let mut timestamps = Vec::new();
+        for (key, value) in &options {
+            match key.as_str() {
+                "collection" => collection = Some(unquote(value).to_string()),
+                "timestamps" => timestamps = timestamp_fields(value),
+                _ => {}
+            }
+        }
+
+        let jsdoc = extract_jsdoc(&content, start);
+        let name = model_names
+            .get(&schema)
+            .cloned()
+            .unwrap_or_else(|| model_name_from_schema(&schema));
+
+        models.push(MongooseModel {
+            name,
+            indexes: extract_indexes(&code, &schema)?,
+            schema,
+            collection,
+            description: jsdoc.description,
+            source_file: path.to_path_buf(),
+            line: count_lines(&content[..start]) + 1,
+            fields,
+            timestamps,
+        });
+    }
+
+    Ok(models)
+}
+
+/// Normalize a model for cross-adapter comparison.
+///
+/// Mongo always stores `_id`, exposed by the adapter as `id`. A field is
+/// non-nullable when it is required or has a non-null default.
+pub fn to_entity(model: &MongooseModel) -> EntityModel {
+    let mut fields = vec![EntityField {
+        name: "id".to_string(),
+        type_name: "ObjectId".to_string(),
+        nullable: false,
+        line: model.line,
+    }];
+
+    for field in &model.fields {
+        let has_default = field
+            .default
+            .as_deref()
+            .is_some_and(|d| d != "null" && d != "undefined");
+        fields.push(EntityField {
+            name: field.path.clone(),
+            type_name: field.type_name.clone(),
+            nullable: !field.required && !has_default,
+            line: model.line,
+        });
+    }
+
+    for timestamp in &model.timestamps {
+        fields.push(EntityField {
+            name: timestamp.clone(),
+            type_name: "Date".to_string(),
+            nullable: false,
+            line: model.line,
+        });
+    }
+
+    let collection = model
+        .collection
+        .clone()
+        .unwrap_or_else(|| model.name.clone());
+    EntityModel {
+        entity: collection.clone(),
+        source: format!("mongodb:{}", collection),
+        file: model.source_file.clone(),
+        line: model.line,
+        fields,
+    }
+}
+
+/// Walk a schema definition object, flattening nested paths
+fn collect_fields(body: &str, prefix: &str, fields: &mut Vec<SchemaField>) {
+    for (key, value) in object_entries(body) {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        let value = value.trim();
+        if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            let entries = object_entries(inner);
+            if entries.iter().any(|(k, _)| k == "type") {
+                fields.push(parse_field_options(path, &entries));
+            } else {
+                // No `type` key: this is a nested sub-document
+                collect_fields(inner, &path, fields);
+            }
+        } else {
+            fields.push(SchemaField {
+                path,
+                type_name: normalize_type(value),
+                required: false,
+                default: None,
+                index: false,
+                unique: false,
+                sparse: false,
+                expires: None,
+                enum_values: Vec::new(),
+                reference: None,
+            });
+        }
+    }
+}
+
+fn parse_field_options(path: String, entries: &[(String, String)]) -> SchemaField {
+    let mut field = SchemaField {
+        path,
+        type_name: "Mixed".to_string(),
+        required: false,
+        default: None,
+        index: false,
+        unique: false,
+        sparse: false,
+        expires: None,
+        enum_values: Vec::new(),
+        reference: None,
+    };
+
+    for (key, value) in entries {
+        let value = value.trim();
+        match key.as_str() {
+            "type" => field.type_name = normalize_type(value),
+            // `required: [true, 'message']` is also truthy
+            "required" => field.required = value == "true" || value.starts_with("[true"),
+            "default" => field.default = Some(value.to_string()),
+            "index" => field.index = value == "true",
+            "unique" => field.unique = value == "true",
+            "sparse" => field.sparse = value == "true",
+            "expires" => field.expires = Some(unquote(value).to_string()),
+            "ref" => field.reference = Some(unquote(value).to_string()),
+            "enum" => {
+                if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    field.enum_values = list
+                        .split(',')
+                        .map(|v| unquote(v).to_string())
+                        .filter(|v| !v.is_empty())
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    field
+}
+
+/// Collect `schema.index({ ... }, { ... })` calls for a schema variable
+fn extract_indexes(code: &str, schema: &str) -> Result<Vec<SchemaIndex>> {
+    let index_re = regex::Regex::new(&format!(
+        r"\b{}\.index\(\s*\{{([^}}]*)\}}\s*(?:,\s*\{{([^}}]*)\}})?\s*\)",
+        regex::escape(schema)
+    ))?;
+
+    let mut indexes = Vec::new();
+    for cap in index_re.captures_iter(code) {
+        let fields = object_entries(&cap[1])
+            .into_iter()
+            .map(|(k, v)| (k, unquote(&v).to_string()))
+            .collect();
+
+        let mut index = SchemaIndex {
+            fields,
+            unique: false,
+            sparse: false,
+            expire_after_seconds: None,
+        };
+
+        if let Some(options) = cap.get(2) {
+            for (key, value) in object_entries(options.as_str()) {
+                match key.as_str() {
+                    "unique" => index.unique = value == "true",
+                    "sparse" => index.sparse = value == "true",
+                    "expireAfterSeconds" => index.expire_after_seconds = Some(value),
+                    _ => {}
+                }
+            }
+        }
+
+        indexes.push(index);
+    }
+
+    Ok(indexes)
+}
+
+/// Resolve the `timestamps` schema option into field names
+fn timestamp_fields(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value == "true" {
+        return vec!["createdAt".to_string(), "updatedAt".to_string()];
+    }
+
+    let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    for (key, value) in object_entries(inner) {
+        let value = value.trim();
+        if value == "false" {
+            continue;
+        }
+        // `createdAt: 'created_at'` renames the field
+        let name = if value == "true" {
+            key
+        } else {
+            unquote(value).to_string()
+        };
+        fields.push(name);
+    }
+    fields
+}
+
+fn normalize_type(value: &str) -> String {
+    let value = value.trim();
+    if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return format!("[{}]", normalize_type(inner));
+    }
+    value
+        .trim_start_matches("mongoose.")
+        .trim_start_matches("Schema.Types.")
+        .trim_start_matches("Types.")
+        .to_string()
+}
+
+/// `userSchema` -> `User`, used when no `.model()` call names the schema
+fn model_name_from_schema(schema: &str) -> String {
+    let base = schema.strip_suffix("Schema").unwrap_or(schema);
+    let mut chars = base.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => schema.to_string(),
+    }
+}
@@ -0,0 +1,562 @@
+//! Configuration options extractor
+//!
+//! Flattens the nested `*Config`/`*Options` interfaces a package accepts
+//! into option paths (`jwt.accessTokenTtl`) and resolves each option's
+//! effective default from the code that applies it.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::typescript::{
+    blank_comments, count_lines, matching_delimiter, object_entries, parse_members, split_top_level,
+};
+use crate::types::{ConfigDefault, ConfigOption, Export, ExportKind, Property};
+
+/// Default values by option path, with the file they were found in
+struct Source {
+    file: String,
+    values: BTreeMap<String, String>,
+}
+
+/// Default sources per interface name, in source order
+type Defaults = HashMap<String, Vec<Source>>;
+
+/// Defaults found in the package's code
+#[derive(Default)]
+struct CodeDefaults {
+    /// Applied where an options object is consumed, per its type:
+    /// destructuring defaults and default objects spread into its members
+    consumers: Defaults,
+
+    /// Default objects per the type they describe
+    /// (`const DEFAULTS: Required<JwtConfig> = {...}`), including constants
+    /// of the same name declared untyped elsewhere
+    objects: Defaults,
+}
+
+/// Extract the flattened configuration options of a package.
+///
+/// Roots are `*Config`/`*Options` interfaces no other configuration
+/// interface nests. A default applied by the code consuming the root, in
+/// destructuring defaults (`const { ttl = '15m' } = options`) or default
+/// objects spread into one of its members (`{ ...DEFAULTS, ...cookies }`),
+/// is the effective one. Otherwise the defaults of the member's own
+/// interface are used: the code consuming that interface, its default
+/// objects, and without any of those `@defaultValue` tags and
+/// `(default: ...)` notes in the description. When the code disagrees with
+/// itself every default is kept.
+pub fn extract_options(exports: &[Export], sources: &[PathBuf]) -> Result<Vec<ConfigOption>> {
+    let mut interfaces: HashMap<&str, &Export> = HashMap::new();
+    for export in exports.iter().filter(|e| e.kind == ExportKind::Interface) {
+        interfaces.entry(export.name.as_str()).or_insert(export);
+    }
+
+    let is_config = |name: &str| name.ends_with("Config") || name.ends_with("Options");
+    let nested: HashSet<&str> = interfaces
+        .values()
+        .filter(|e| is_config(&e.name))
+        .flat_map(|e| &e.properties)
+        .flat_map(|m| type_words(&m.type_annotation))
+        .collect();
+
+    let mut roots: Vec<&Export> = interfaces
+        .values()
+        .copied()
+        .filter(|e| is_config(&e.name) && !nested.contains(e.name.as_str()))
+        .filter(|e| !e.properties.is_empty() && e.properties.iter().all(|m| !m.method))
+        .collect();
+    roots.sort_by(|a, b| (&a.source_file, a.line).cmp(&(&b.source_file, b.line)));
+
+    let defaults = code_defaults(sources)?;
+
+    let mut walker = Walker {
+        root: String::new(),
+        interfaces: &interfaces,
+        defaults: &defaults,
+        options: Vec::new(),
+    };
+    for root in roots {
+        walker.root = root.name.clone();
+        let scope = Scope {
+            consumer: (root.name.clone(), String::new()),
+            own: (root.name.clone(), String::new()),
+        };
+        walker.walk(root, "", &scope, true, 0);
+    }
+
+    Ok(walker.options)
+}
+
+/// Where the defaults of an interface's members are looked up: each
+/// interface name with the members' path relative to it
+struct Scope {
+    /// The root options object, whose consuming code decides
+    consumer: (String, String),
+
+    /// The interface declaring the members
+    own: (String, String),
+}
+
+impl Scope {
+    fn nest(&self, name: &str) -> Scope {
+        Scope {
+            consumer: (self.consumer.0.clone(), join_path(&self.consumer.1, name)),
+            own: (self.own.0.clone(), join_path(&self.own.1, name)),
+        }
+    }
+}
+
+struct Walker<'a> {
+    root: String,
+    interfaces: &'a HashMap<&'a str, &'a Export>,
+    defaults: &'a CodeDefaults,
+    options: Vec<ConfigOption>,
+}
+
+impl Walker<'_> {
+    fn walk(
+        &mut self,
+        interface: &Export,
+        prefix: &str,
+        scope: &Scope,
+        required: bool,
+        depth: usize,
+    ) {
+        self.walk_members(
+            &interface.properties,
+            &interface.source_file,
+            scope,
+            prefix,
+            required,
+            depth,
+        );
+    }
+
+    fn walk_members(
+        &mut self,
+        members: &[Property],
+        source_file: &Path,
+        scope: &Scope,
+        prefix: &str,
+        required: bool,
+        depth: usize,
+    ) {
+        for member in members.iter().filter(|m| !m.method) {
+            let path = join_path(prefix, &member.name);
+            let (type_name, partial) = unwrap_utility(&member.type_annotation);
+            let member_required = required && !member.optional;
+
+            let mut candidates = self.candidates(scope, member);
+            let default = (candidates.len() == 1).then(|| candidates.remove(0).value);
+
+            self.options.push(ConfigOption {
+                root: self.root.clone(),
+                path: path.clone(),
+                type_annotation: member.type_annotation.clone(),
+                required: member_required,
+                description: member.description.clone(),
+                default,
+                disputed_defaults: candidates,
+                source_file: source_file.to_path_buf(),
+                line: member.line,
+            });
+
+            if depth >= 4 {
+                continue;
+            }
+            let children_required = member_required && !partial;
+            let inner = scope.nest(&member.name);
+
+            // Inline object types keep using the enclosing interface's defaults
+            if let Some(body) = type_name
+                .strip_prefix('{')
+                .and_then(|t| t.strip_suffix('}'))
+            {
+                let children = source_members(source_file, member)
+                    .unwrap_or_else(|| inline_members(body, member.line));
+                self.walk_members(
+                    &children,
+                    source_file,
+                    &inner,
+                    &path,
+                    children_required,
+                    depth + 1,
+                );
+            } else if let Some(nested) = self.interfaces.get(type_name) {
+                // Interfaces with methods are services, not options
+                if !nested.properties.is_empty() && nested.properties.iter().all(|m| !m.method) {
+                    let scope = Scope {
+                        consumer: inner.consumer,
+                        own: (nested.name.clone(), String::new()),
+                    };
+                    self.walk(nested, &path, &scope, children_required, depth + 1);
+                }
+            }
+        }
+    }
+
+    /// The distinct defaults of a member: the one applied by the root's
+    /// consuming code, or else every default of its own interface
+    fn candidates(&self, scope: &Scope, member: &Property) -> Vec<ConfigDefault> {
+        let lookup = |defaults: &Defaults, (name, relative): &(String, String)| {
+            let path = join_path(relative, &member.name);
+            defaults
+                .get(name)
+                .into_iter()
+                .flatten()
+                .filter_map(|source| {
+                    let value = source.values.get(&path)?;
+                    (!is_empty_object(value)).then(|| ConfigDefault {
+                        value: value.clone(),
+                        source: source.file.clone(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut candidates = lookup(&self.defaults.consumers, &scope.consumer);
+        if candidates.is_empty() {
+            if scope.own.0 != scope.consumer.0 {
+                candidates = lookup(&self.defaults.consumers, &scope.own);
+            }
+            candidates.extend(lookup(&self.defaults.objects, &scope.own));
+        }
+        if candidates.is_empty() {
+            let documented = member
+                .default
+                .clone()
+                .or_else(|| member.description.as_deref().and_then(description_default));
+            candidates.extend(documented.map(|value| ConfigDefault {
+                value,
+                source: "JSDoc".to_string(),
+            }));
+        }
+
+        // The same value from several places, however quoted, is one default
+        let mut seen = HashSet::new();
+        candidates.retain(|c| seen.insert(c.value.replace('"', "'")));
+        candidates
+    }
+}
+
+/// Members of a multi-line inline object type, read from the source so
+/// their JSDoc (blanked in the member's type) is kept
+fn source_members(source_file: &Path, member: &Property) -> Option<Vec<Property>> {
+    let content = std::fs::read_to_string(source_file).ok()?;
+    let line_start: usize = content
+        .split_inclusive('\n')
+        .take(member.line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let code = blank_comments(&content);
+    let open = line_start + code[line_start..].find('{')?;
+    let close = matching_delimiter(&code, open)?;
+    if !code[open..close].contains('\n') {
+        return None;
+    }
+    Some(parse_members(
+        &content[open + 1..close],
+        count_lines(&content[..open]) + 1,
+    ))
+}
+
+/// Members of an inline object type, which may sit on a single line
+fn inline_members(body: &str, line: usize) -> Vec<Property> {
+    if body.contains('\n') {
+        return parse_members(body, line);
+    }
+    let body = split_top_level(body, ';')
+        .iter()
+        .flat_map(|part| split_top_level(part, ','))
+        .collect::<Vec<_>>()
+        .join(";\n");
+    let mut members = parse_members(&body, line);
+    for member in &mut members {
+        member.line = line;
+    }
+    members
+}
+
+/// An object constant, flattened, with the type it is declared as
+struct ObjectConstant {
+    file: usize,
+    name: String,
+    type_name: Option<String>,
+    values: BTreeMap<String, String>,
+}
+
+/// Collect defaults from default objects, destructuring defaults and
+/// default objects spread over destructured members
+fn code_defaults(sources: &[PathBuf]) -> Result<CodeDefaults> {
+    let object_re = regex::Regex::new(
+        r"(?m)^(?:export\s+)?const\s+(\w+)\s*(?::\s*(?:(?:Required|Partial|Readonly)\s*<\s*)?(\w+)\s*>?\s*)?=\s*\{",
+    )?;
+    let destructure_re = regex::Regex::new(r"\bconst\s*\{")?;
+    let constant_re = regex::Regex::new(
+        r"(?m)^(?:export\s+)?const\s+([A-Z][A-Z0-9_]*)\s*(?::[^=]+)?=\s*([^;]+);",
+    )?;
+    let spread_re = regex::Regex::new(r"\{\s*\.\.\.(\w+)\s*,\s*\.\.\.(\w+)(?:\.(\w+))?\s*\}")?;
+
+    let mut files = Vec::new();
+    let mut constants: HashMap<String, String> = HashMap::new();
+    for source in sources {
+        let content = std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let code = blank_comments(&content);
+        for cap in constant_re.captures_iter(&code) {
+            constants
+                .entry(cap[1].to_string())
+                .or_insert_with(|| collapse(&cap[2]));
+        }
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        files.push((name, code));
+    }
+    let resolve = |value: &str| {
+        let value = collapse(value);
+        let value = value
+            .strip_suffix(" as const")
+            .unwrap_or(&value)
+            .to_string();
+        constants.get(&value).cloned().unwrap_or(value)
+    };
+
+    let mut objects = Vec::new();
+    for (index, (_, code)) in files.iter().enumerate() {
+        for cap in object_re.captures_iter(code) {
+            let open = cap.get(0).unwrap().end() - 1;
+            let Some(close) = matching_delimiter(code, open) else {
+                continue;
+            };
+            let mut values = BTreeMap::new();
+            flatten_object(&code[open + 1..close], "", &resolve, &mut values);
+            objects.push(ObjectConstant {
+                file: index,
+                name: cap[1].to_string(),
+                type_name: cap.get(2).map(|t| t.as_str().to_string()),
+                values,
+            });
+        }
+    }
+
+    let mut defaults = CodeDefaults::default();
+    for object in &objects {
+        let Some(type_name) = &object.type_name else {
+            continue;
+        };
+        for other in objects.iter().filter(|o| o.name == object.name) {
+            push(
+                &mut defaults.objects,
+                type_name,
+                &files[other.file].0,
+                &other.values,
+            );
+        }
+    }
+
+    for (index, (file, code)) in files.iter().enumerate() {
+        // Destructured members by local name, with the options type and key
+        let mut aliases: HashMap<String, (String, String)> = HashMap::new();
+
+        for m in destructure_re.find_iter(code) {
+            let open = m.end() - 1;
+            let Some(close) = matching_delimiter(code, open) else {
+                continue;
+            };
+            let rest = code[close + 1..].trim_start();
+            let Some(ident) = rest
+                .strip_prefix('=')
+                .map(|r| r.trim_start())
+                .map(|r| {
+                    r.split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .next()
+                        .unwrap_or("")
+                })
+                .filter(|i| !i.is_empty())
+            else {
+                continue;
+            };
+            let Some(type_name) = parameter_type(&code[..m.start()], ident) else {
+                continue;
+            };
+
+            let mut values = BTreeMap::new();
+            for binding in split_top_level(&code[open + 1..close], ',') {
+                let (target, value) = split_default(&binding).unwrap_or((binding.trim(), ""));
+                let (key, alias) = target.split_once(':').unwrap_or((target, target));
+                let (key, alias) = (key.trim(), alias.trim());
+                if key.is_empty() || key.starts_with("...") {
+                    continue;
+                }
+                aliases.insert(alias.to_string(), (type_name.clone(), key.to_string()));
+                if !value.is_empty() {
+                    values
+                        .entry(key.to_string())
+                        .or_insert_with(|| resolve(value));
+                }
+            }
+            push(&mut defaults.consumers, &type_name, file, &values);
+        }
+
+        // `{ ...DEFAULTS, ...cookies }` or `{ ...DEFAULTS, ...options.cookies }`
+        for cap in spread_re.captures_iter(code) {
+            let target = match cap.get(3) {
+                Some(key) => {
+                    let start = cap.get(0).unwrap().start();
+                    parameter_type(&code[..start], &cap[2]).map(|t| (t, key.as_str().to_string()))
+                }
+                None => aliases.get(&cap[2]).cloned(),
+            };
+            let Some((type_name, key)) = target else {
+                continue;
+            };
+            // The constant declared in the same file, else any
+            let Some(object) = objects
+                .iter()
+                .filter(|o| o.name == cap[1])
+                .min_by_key(|o| o.file != index)
+            else {
+                continue;
+            };
+            let values: BTreeMap<_, _> = object
+                .values
+                .iter()
+                .map(|(path, value)| (join_path(&key, path), value.clone()))
+                .collect();
+            push(&mut defaults.consumers, &type_name, file, &values);
+        }
+    }
+
+    Ok(defaults)
+}
+
+/// Add `values` found in `file` to the defaults of `type_name`
+fn push(defaults: &mut Defaults, type_name: &str, file: &str, values: &BTreeMap<String, String>) {
+    if values.is_empty() {
+        return;
+    }
+    let sources = defaults.entry(type_name.to_string()).or_default();
+    match sources.iter_mut().find(|s| s.file == file) {
+        Some(source) => {
+            for (path, value) in values {
+                source.values.entry(path.clone()).or_insert(value.clone());
+            }
+        }
+        None => sources.push(Source {
+            file: file.to_string(),
+            values: values.clone(),
+        }),
+    }
+}
+
+/// Flatten `{ a: { b: 1 } }` into `a` and `a.b`
+fn flatten_object(
+    body: &str,
+    prefix: &str,
+    resolve: &dyn Fn(&str) -> String,
+    out: &mut BTreeMap<String, String>,
+) {
+    for (key, value) in object_entries(body) {
+        let value = value.trim();
+        if value.starts_with("undefined") {
+            continue;
+        }
+        let path = join_path(prefix, &key);
+        if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            flatten_object(inner, &path, resolve, out);
+        }
+        out.entry(path).or_insert_with(|| resolve(value));
+    }
+}
+
+/// The type of `ident` as a parameter declared before `code` ends:
+/// `function f(options: T)` or `FastifyPluginAsync<T> = async (app, options)`
+fn parameter_type(code: &str, ident: &str) -> Option<String> {
+    let ident = regex::escape(ident);
+    let annotated = regex::Regex::new(&format!(r"[(,]\s*{}\s*\??\s*:\s*(\w+)", ident)).ok()?;
+    let generic = regex::Regex::new(&format!(
+        r"<\s*(\w+)\s*>\s*=\s*(?:async\s*)?\([^)]*\b{}\b",
+        ident
+    ))
+    .ok()?;
+
+    let last = |re: &regex::Regex| {
+        re.captures_iter(code)
+            .last()
+            .map(|c| (c.get(0).unwrap().start(), c[1].to_string()))
+    };
+    match (last(&annotated), last(&generic)) {
+        (Some(a), Some(g)) => Some(if a.0 > g.0 { a.1 } else { g.1 }),
+        (a, g) => a.or(g).map(|(_, t)| t),
+    }
+}
+
+/// Split `name = value` at the top-level `=`, ignoring `=>`
+fn split_default(binding: &str) -> Option<(&str, &str)> {
+    let bytes = binding.as_bytes();
+    let mut depth = 0i32;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'{' | b'[' | b'(' | b'<' => depth += 1,
+            b'>' if i > 0 && bytes[i - 1] == b'=' => {}
+            b'}' | b']' | b')' | b'>' => depth -= 1,
+            b'=' if depth == 0
+                && bytes.get(i + 1) != Some(&b'>')
+                && bytes.get(i + 1) != Some(&b'=') =>
+            {
+                return Some((binding[..i].trim(), binding[i + 1..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `Partial<T>` -> (`T`, true); strips `Required<>`/`Readonly<>` and `| undefined`
+fn unwrap_utility(type_annotation: &str) -> (&str, bool) {
+    let mut t = type_annotation.trim();
+    t = t.strip_suffix("| undefined").map_or(t, |s| s.trim());
+    if let Some(inner) = t.strip_prefix("Partial<").and_then(|s| s.strip_suffix('>')) {
+        return (inner.trim(), true);
+    }
+    for wrapper in ["Required<", "Readonly<"] {
+        if let Some(inner) = t.strip_prefix(wrapper).and_then(|s| s.strip_suffix('>')) {
+            return (inner.trim(), false);
+        }
+    }
+    (t, false)
+}
+
+/// `(default: 5)` or `(default: "lax")` in a description
+fn description_default(description: &str) -> Option<String> {
+    let start = description.find("(default:")? + "(default:".len();
+    let rest = &description[start..];
+    let end = rest.find(')')?;
+    Some(rest[..end].trim().to_string()).filter(|d| !d.is_empty())
+}
+
+fn type_words(type_annotation: &str) -> Vec<&str> {
+    type_annotation
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn is_empty_object(value: &str) -> bool {
+    value.replace(char::is_whitespace, "") == "{}"
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn collapse(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
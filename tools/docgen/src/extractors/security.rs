@@ -0,0 +1,145 @@
+//! Security notes extractor
+//!
+//! Collects the protections source comments claim, from `@security` tags
+//! and `Security:`/`Security Policy:` bullet lists in JSDoc blocks, with
+//! the symbol (or `@module`) each block documents.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::typescript::count_lines;
+use crate::types::SecurityNote;
+
+/// Extract the security notes of every source file
+pub fn extract_notes(sources: &[PathBuf]) -> Result<Vec<SecurityNote>> {
+    let mut notes = Vec::new();
+    for source in sources {
+        notes.extend(extract_file(source)?);
+    }
+    Ok(notes)
+}
+
+fn extract_file(path: &Path) -> Result<Vec<SecurityNote>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let block_re = regex::Regex::new(r"(?s)/\*\*(.*?)\*/")?;
+    let symbol_re = regex::Regex::new(
+        r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(?:function\*?|const|let|var|class|interface|type|enum)\s+([A-Za-z_$][\w$]*)",
+    )?;
+    let member_re = regex::Regex::new(
+        r"^(?:(?:public|protected|static|readonly|async)\s+)*([A-Za-z_$][\w$]*)\s*[(<:?]",
+    )?;
+
+    let mut notes = Vec::new();
+    for cap in block_re.captures_iter(&content) {
+        let block = cap.get(0).unwrap();
+        let lines: Vec<&str> = cap[1]
+            .lines()
+            .map(|l| l.trim().trim_start_matches('*').trim())
+            .collect();
+
+        let claims = security_claims(&lines);
+        if claims.is_empty() {
+            continue;
+        }
+
+        // A file header names its module; other blocks document what follows
+        let module = lines
+            .iter()
+            .find_map(|l| l.strip_prefix("@module"))
+            .map(|m| m.trim().to_string());
+        let after = content[block.end()..].trim_start();
+        let declared = symbol_re
+            .captures(after)
+            .or_else(|| member_re.captures(after))
+            .map(|c| c[1].to_string());
+
+        let (symbol, line) = match (module, declared) {
+            (Some(module), _) => (module, count_lines(&content[..block.start()]) + 1),
+            (None, Some(symbol)) => {
+                let offset = content.len() - after.len();
+                (symbol, count_lines(&content[..offset]) + 1)
+            }
+            (None, None) => (
+                path.file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                count_lines(&content[..block.start()]) + 1,
+            ),
+        };
+
+        let summary = lines
+            .iter()
+            .take_while(|l| !l.is_empty() && !l.starts_with('@'))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        notes.push(SecurityNote {
+            symbol,
+            summary: (!summary.is_empty()).then_some(summary),
+            claims,
+            source_file: path.to_path_buf(),
+            line,
+        });
+    }
+
+    Ok(notes)
+}
+
+/// Claims from `Security:` bullet lists and `@security` tags
+fn security_claims(lines: &[&str]) -> Vec<String> {
+    let heading_re =
+        regex::Regex::new(r"^Security(?:\s+[A-Z]\w*)*:\s*(.*)$").expect("valid heading regex");
+
+    let mut claims: Vec<String> = Vec::new();
+    // Whether lines continue a `Security:` list or an `@security` tag
+    let mut in_list = false;
+    let mut in_tag = false;
+    let mut list_start = 0;
+
+    for line in lines {
+        if let Some(cap) = heading_re.captures(line) {
+            in_list = true;
+            in_tag = false;
+            list_start = claims.len();
+            if !cap[1].trim().is_empty() {
+                claims.push(cap[1].trim().to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("@security") {
+            in_list = false;
+            in_tag = true;
+            claims.push(rest.trim().to_string());
+        } else if line.starts_with('@') {
+            in_list = false;
+            in_tag = false;
+        } else if line.is_empty() {
+            in_tag = false;
+            // A blank line right under the heading does not end the list
+            if claims.len() > list_start {
+                in_list = false;
+            }
+        } else if in_list {
+            match line.strip_prefix("- ").or_else(|| line.strip_prefix("• ")) {
+                Some(item) => claims.push(item.trim().to_string()),
+                None => append(&mut claims, line),
+            }
+        } else if in_tag {
+            append(&mut claims, line);
+        }
+    }
+
+    claims.retain(|c| !c.is_empty());
+    claims
+}
+
+fn append(claims: &mut [String], line: &str) {
+    if let Some(last) = claims.last_mut() {
+        if !last.is_empty() {
+            last.push(' ');
+        }
+        last.push_str(line.trim());
+    }
+}
@@ -0,0 +1,109 @@
+//! SQL DDL extractor
+//!
+//! Reads `CREATE TABLE` statements (as shipped in the Oracle adapter's
+//! `schema.sql`) into storage-agnostic entity models.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::debug;
+
+use super::typescript::{count_lines, matching_delimiter, split_top_level};
+use crate::types::{EntityField, EntityModel};
+
+/// Extract table definitions from a SQL file
+pub fn extract_tables(path: &Path, dialect: &str) -> Result<Vec<EntityModel>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_sql_comments(&content);
+
+    debug!("Extracting SQL tables from {}", path.display());
+
+    let table_re = regex::Regex::new(
+        r#"(?i)\bCREATE\s+TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?["`]?(\w+)["`]?\s*\("#,
+    )?;
+    let constraint_re =
+        regex::Regex::new(r"(?i)^(CONSTRAINT|PRIMARY|FOREIGN|UNIQUE|CHECK|INDEX|KEY)\b")?;
+    let column_re = regex::Regex::new(r#"^["`]?(\w+)["`]?\s+(\w+(?:\s*\([^)]*\))?)"#)?;
+
+    let mut tables = Vec::new();
+
+    for cap in table_re.captures_iter(&code) {
+        let name = cap[1].to_string();
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        let mut offset = open + 1;
+        for column in split_top_level(&code[open + 1..close], ',') {
+            let column_start = offset + (column.len() - column.trim_start().len());
+            offset += column.len() + 1;
+
+            let column = column.trim();
+            if constraint_re.is_match(column) {
+                continue;
+            }
+            let Some(col) = column_re.captures(column) else {
+                continue;
+            };
+
+            let upper = column.to_uppercase();
+            let nullable = !upper.contains("NOT NULL") && !upper.contains("PRIMARY KEY");
+
+            fields.push(EntityField {
+                name: camel_case(&col[1]),
+                type_name: col[2].split_whitespace().collect::<Vec<_>>().join(""),
+                nullable,
+                line: count_lines(&content[..column_start]) + 1,
+            });
+        }
+
+        tables.push(EntityModel {
+            entity: name.clone(),
+            source: format!("{}:{}", dialect, name),
+            file: path.to_path_buf(),
+            line: count_lines(&content[..cap.get(0).unwrap().start()]) + 1,
+            fields,
+        });
+    }
+
+    Ok(tables)
+}
+
+/// `APPLE_USER_ID` / `apple_user_id` -> `appleUserId`
+pub fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for (i, part) in name.split('_').filter(|p| !p.is_empty()).enumerate() {
+        let lower = part.to_lowercase();
+        if i == 0 {
+            out.push_str(&lower);
+        } else {
+            let mut chars = lower.chars();
+            if let Some(first) = chars.next() {
+                out.extend(first.to_uppercase());
+                out.push_str(chars.as_str());
+            }
+        }
+    }
+    out
+}
+
+/// Blank `--` and `/* */` comments, keeping byte offsets and newlines
+fn blank_sql_comments(content: &str) -> String {
+    let comment_re = regex::Regex::new(r"(?s)--[^\n]*|/\*.*?\*/").expect("valid comment regex");
+    comment_re
+        .replace_all(content, |caps: &regex::Captures| {
+            caps[0]
+                .chars()
+                .map(|c| {
+                    if c == '\n' {
+                        "\n".to_string()
+                    } else {
+                        " ".repeat(c.len_utf8())
+                    }
+                })
+                .collect::<String>()
+        })
+        .into_owned()
+}
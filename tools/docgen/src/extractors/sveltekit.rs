@@ -0,0 +1,301 @@
+//! SvelteKit integration surface extractor
+//!
+//! A SvelteKit package integrates through hooks, filesystem routes and
+//! `App.Locals` rather than plain exports. This module finds the `handle`
+//! style hooks (exported from a hooks file or typed `Handle`, `HandleFetch`,
+//! ... inside a factory) with the `event.locals` fields they assign, the
+//! handlers, `load` and form actions of every route file under
+//! `src/routes`, and the fields declared on `App.Locals`.
+
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{
+    blank_comments, count_lines, extract_jsdoc, matching_delimiter, object_entries, parse_members,
+};
+use crate::types::{Property, SvelteKitHandler, SvelteKitHook, SvelteKitRoute};
+
+/// Hook types exported by `@sveltejs/kit`
+const HOOK_TYPES: &str =
+    "Handle|HandleFetch|HandleServerError|HandleClientError|HandleValidationError|Reroute|Transport|ServerInit|ClientInit";
+
+/// Names SvelteKit looks up in `hooks.server`, `hooks.client` and `hooks`
+const HOOK_NAMES: [&str; 6] = [
+    "handle",
+    "handleFetch",
+    "handleError",
+    "reroute",
+    "transport",
+    "init",
+];
+
+/// Names SvelteKit looks up in route files besides `actions`
+const ROUTE_EXPORTS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS", "HEAD", "fallback", "load",
+];
+
+/// Extract the hooks defined in the package sources
+pub fn extract_hooks(sources: &[PathBuf]) -> Result<Vec<SvelteKitHook>> {
+    let typed_re = regex::Regex::new(&format!(
+        r"\b(?:const|let)\s+([A-Za-z_$][\w$]*)\s*:\s*(?:\w+\.)?({})\b\s*=",
+        HOOK_TYPES
+    ))?;
+    let exported_re = regex::Regex::new(
+        r"(?m)^export\s+(?:async\s+)?(?:function\s+([A-Za-z_$][\w$]*)|const\s+([A-Za-z_$][\w$]*)\s*(?::\s*(?:\w+\.)?(\w+))?\s*=)",
+    )?;
+    let destructured_re = regex::Regex::new(r"(?m)^export\s+const\s*\{([^}]*)\}\s*=")?;
+
+    let mut hooks: Vec<SvelteKitHook> = Vec::new();
+    for source in sources {
+        let content = std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let code = blank_comments(&content);
+        let is_hooks_file = source
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("hooks."));
+
+        let mut found: Vec<(String, Option<String>, usize)> = Vec::new();
+        for cap in typed_re.captures_iter(&code) {
+            found.push((
+                cap[1].to_string(),
+                Some(cap[2].to_string()),
+                cap.get(0).unwrap().start(),
+            ));
+        }
+
+        // A hooks file declares hooks by name, with or without a type
+        if is_hooks_file {
+            for cap in exported_re.captures_iter(&code) {
+                let name = cap.get(1).or(cap.get(2)).unwrap().as_str();
+                if HOOK_NAMES.contains(&name) {
+                    let hook_type = cap.get(3).map(|t| t.as_str().to_string());
+                    found.push((name.to_string(), hook_type, cap.get(0).unwrap().start()));
+                }
+            }
+            for cap in destructured_re.captures_iter(&code) {
+                for name in cap[1].split(',') {
+                    let name = name.split(':').next_back().unwrap_or_default().trim();
+                    if HOOK_NAMES.contains(&name) {
+                        found.push((name.to_string(), None, cap.get(0).unwrap().start()));
+                    }
+                }
+            }
+        }
+
+        found.sort_by_key(|(_, _, start)| *start);
+        for (name, hook_type, start) in found {
+            // The exported `const handle: Handle = ...` matches both patterns
+            let line = count_lines(&content[..start]) + 1;
+            if hooks
+                .iter()
+                .any(|h| h.source_file == *source && h.line == line && h.name == name)
+            {
+                continue;
+            }
+
+            debug!("Found SvelteKit hook {} in {}", name, source.display());
+            let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+            hooks.push(SvelteKitHook {
+                name,
+                hook_type,
+                description: extract_jsdoc(&content, line_start).description,
+                locals_set: locals_assigned(&code, start),
+                source_file: source.clone(),
+                line,
+            });
+        }
+    }
+
+    Ok(hooks)
+}
+
+/// `locals` fields assigned in the body of the declaration at `start`
+fn locals_assigned(code: &str, start: usize) -> Vec<String> {
+    let Some(body) = declaration_body(code, start) else {
+        return Vec::new();
+    };
+    let assign_re = regex::Regex::new(
+        r#"\blocals\s*(?:\.\s*([A-Za-z_$][\w$]*)|\[\s*['"]([^'"]+)['"]\s*\])\s*=[^=>]"#,
+    )
+    .expect("valid locals regex");
+
+    let mut fields: Vec<String> = Vec::new();
+    for cap in assign_re.captures_iter(body) {
+        let field = cap.get(1).or(cap.get(2)).unwrap().as_str().to_string();
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+/// Text of the first `{ ... }` block after `start` that is not a
+/// destructuring parameter (`({ event, resolve }) => {`)
+fn declaration_body(code: &str, start: usize) -> Option<&str> {
+    let mut from = start;
+    loop {
+        let open = from + code[from..].find('{')?;
+        let close = matching_delimiter(code, open)?;
+        let before = code[..open].trim_end();
+        if !(before.ends_with('(') || before.ends_with(',')) {
+            return Some(&code[open..=close]);
+        }
+        from = close + 1;
+    }
+}
+
+/// Extract the route files under `src/routes`
+pub fn extract_routes(pkg_path: &Path) -> Result<Vec<SvelteKitRoute>> {
+    let routes_dir = pkg_path.join("src").join("routes");
+    if !routes_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<_> = WalkDir::new(&routes_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_route_file(&e.file_name().to_string_lossy()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let export_re = regex::Regex::new(
+        r"(?m)^export\s+(?:async\s+)?(?:function\s+([A-Za-z_$][\w$]*)|const\s+([A-Za-z_$][\w$]*))",
+    )?;
+    let read_re = regex::Regex::new(r"\blocals\s*\.\s*([A-Za-z_$][\w$]*)")?;
+
+    let mut routes = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let code = blank_comments(&content);
+
+        let mut handlers = Vec::new();
+        let mut actions = Vec::new();
+        for cap in export_re.captures_iter(&code) {
+            let name = cap.get(1).or(cap.get(2)).unwrap().as_str();
+            let start = cap.get(0).unwrap().start();
+            if name == "actions" {
+                actions = form_actions(&content, &code, start);
+            } else if ROUTE_EXPORTS.contains(&name) {
+                handlers.push(SvelteKitHandler {
+                    name: name.to_string(),
+                    description: extract_jsdoc(&content, start).description,
+                    line: count_lines(&content[..start]) + 1,
+                });
+            }
+        }
+
+        let mut locals_used: Vec<String> = Vec::new();
+        for cap in read_re.captures_iter(&code) {
+            if !locals_used.contains(&cap[1].to_string()) {
+                locals_used.push(cap[1].to_string());
+            }
+        }
+
+        let relative = file.strip_prefix(&routes_dir).unwrap_or(&file);
+        routes.push(SvelteKitRoute {
+            path: route_path(relative.parent().unwrap_or(Path::new(""))),
+            file_name: file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            handlers,
+            actions,
+            locals_used,
+            source_file: file,
+        });
+    }
+
+    Ok(routes)
+}
+
+/// `+server`, `+page.server`, `+page`, `+layout.server` and `+layout`
+/// modules; `.svelte` components have no server surface
+fn is_route_file(name: &str) -> bool {
+    let Some((stem, ext)) = name.rsplit_once('.') else {
+        return false;
+    };
+    matches!(ext, "ts" | "js")
+        && matches!(
+            stem,
+            "+server" | "+page.server" | "+page" | "+layout.server" | "+layout"
+        )
+}
+
+/// The URL path of a route directory, without `(group)` segments
+fn route_path(dir: &Path) -> String {
+    let segments: Vec<String> = dir
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .filter(|s| !(s.starts_with('(') && s.ends_with(')')))
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Keys of `export const actions = { ... }` with their JSDoc
+fn form_actions(content: &str, code: &str, start: usize) -> Vec<SvelteKitHandler> {
+    let Some(open) = code[start..].find('{').map(|i| start + i) else {
+        return Vec::new();
+    };
+    let Some(close) = matching_delimiter(code, open) else {
+        return Vec::new();
+    };
+
+    let body = &code[open + 1..close];
+    let mut actions = Vec::new();
+    let mut offset = 0;
+    for (name, _) in object_entries(body) {
+        let name = name.trim_start_matches("async ").trim().to_string();
+        // Method shorthand (`default({ request }) {}`) keeps its parameters
+        let name = name
+            .split(|c: char| c == '(' || c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let at = body[offset..]
+            .find(&name)
+            .map_or(open + 1, |i| open + 1 + offset + i);
+        offset = at - open - 1;
+
+        let line_start = content[..at].rfind('\n').map_or(0, |i| i + 1);
+        actions.push(SvelteKitHandler {
+            description: extract_jsdoc(content, line_start).description,
+            line: count_lines(&content[..at]) + 1,
+            name,
+        });
+    }
+    actions
+}
+
+/// Extract the fields declared on `App.Locals`
+pub fn extract_locals(sources: &[PathBuf]) -> Result<Vec<Property>> {
+    let interface_re = regex::Regex::new(r"\binterface\s+Locals\s*\{")?;
+
+    let mut locals = Vec::new();
+    for source in sources {
+        let content = std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let code = blank_comments(&content);
+        // `Locals` is only SvelteKit's inside the `App` namespace
+        if !code.contains("namespace App") {
+            continue;
+        }
+
+        for m in interface_re.find_iter(&code) {
+            let open = m.end() - 1;
+            let Some(close) = matching_delimiter(&code, open) else {
+                continue;
+            };
+            let first_line = count_lines(&content[..open]) + 1;
+            locals.extend(parse_members(&content[open + 1..close], first_line));
+        }
+    }
+    Ok(locals)
+}
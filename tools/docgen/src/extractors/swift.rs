@@ -0,0 +1,466 @@
+//! Swift source extractor
+//!
+//! Reads the parts of the AppleSignInKit sources other checks need: struct
+//! declarations with their stored properties, and the API calls the SDK
+//! makes against the auth server.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use super::typescript::{
+    blank_comments, count_lines, matching_delimiter, split_top_level, unquote,
+};
+use crate::types::{ClientEndpoint, Property, SwiftManifest, SwiftPlatform, SwiftStruct};
+
+/// JSON key strategies configured on the SDK's `JSONEncoder`/`JSONDecoder`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyStrategies {
+    /// `keyEncodingStrategy = .convertToSnakeCase`
+    pub encode_snake_case: bool,
+
+    /// `keyDecodingStrategy = .convertFromSnakeCase`
+    pub decode_snake_case: bool,
+}
+
+/// Swift packages under `packages/`, found by their `Package.swift`
+/// manifest since they have no package.json. Returns `(name, path)` pairs.
+pub fn find_packages(root: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let packages_dir = root.join("packages");
+    if !packages_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut manifests: Vec<_> = WalkDir::new(&packages_dir)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == "Package.swift")
+        .map(|e| e.into_path())
+        .collect();
+    manifests.sort();
+
+    let name_re = regex::Regex::new(r#"name:\s*"([^"]+)""#)?;
+    let mut packages = Vec::new();
+    for manifest in manifests {
+        let Some(pkg_path) = manifest.parent() else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&manifest)
+            .with_context(|| format!("Failed to read {}", manifest.display()))?;
+        let name = name_re
+            .captures(&content)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| pkg_path.display().to_string());
+        packages.push((name, pkg_path.to_path_buf()));
+    }
+    Ok(packages)
+}
+
+/// Name, tools version, platform minimums and library products from a
+/// package's `Package.swift`, if it has one
+pub fn extract_manifest(pkg_path: &Path) -> Result<Option<SwiftManifest>> {
+    let manifest = pkg_path.join("Package.swift");
+    if !manifest.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&manifest)
+        .with_context(|| format!("Failed to read {}", manifest.display()))?;
+
+    let tools_re = regex::Regex::new(r"//\s*swift-tools-version:\s*([\d.]+)")?;
+    let name_re = regex::Regex::new(r#"Package\(\s*name:\s*"([^"]+)""#)?;
+    let platform_re = regex::Regex::new(r#"\.(\w+)\(\s*(?:\.v(\d+(?:_\d+)*)|"([^"]+)")"#)?;
+    let library_re = regex::Regex::new(r#"\.library\(\s*name:\s*"([^"]+)""#)?;
+    let code = blank_comments(&content);
+
+    let mut platforms = Vec::new();
+    let list = code
+        .find("platforms:")
+        .and_then(|start| code[start..].find('[').map(|i| start + i))
+        .and_then(|open| Some((open, matching_delimiter(&code, open)?)));
+    if let Some((open, close)) = list {
+        for cap in platform_re.captures_iter(&code[open..close]) {
+            let version = cap
+                .get(2)
+                .map(|v| v.as_str().replace('_', "."))
+                .or_else(|| cap.get(3).map(|v| v.as_str().to_string()))
+                .unwrap_or_default();
+            platforms.push(SwiftPlatform {
+                name: cap[1].to_string(),
+                version,
+            });
+        }
+    }
+
+    Ok(Some(SwiftManifest {
+        name: name_re
+            .captures(&code)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| pkg_path.display().to_string()),
+        tools_version: tools_re.captures(&content).map(|c| c[1].to_string()),
+        platforms,
+        products: library_re
+            .captures_iter(&code)
+            .map(|c| c[1].to_string())
+            .collect(),
+    }))
+}
+
+/// Key strategies set anywhere in a package's sources
+pub fn key_strategies(pkg_path: &Path) -> Result<KeyStrategies> {
+    let mut strategies = KeyStrategies::default();
+    for file in source_files(pkg_path) {
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        strategies.encode_snake_case |=
+            content.contains("keyEncodingStrategy = .convertToSnakeCase");
+        strategies.decode_snake_case |=
+            content.contains("keyDecodingStrategy = .convertFromSnakeCase");
+    }
+    Ok(strategies)
+}
+
+/// Swift sources of a package, sorted, excluding tests
+pub fn source_files(pkg_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<_> = WalkDir::new(pkg_path.join("Sources"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "swift"))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Extract struct declarations from a file
+pub fn extract_structs(path: &Path) -> Result<Vec<SwiftStruct>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    let struct_re = regex::Regex::new(
+        r"(?m)^[ \t]*(?:(?:public|internal|private|fileprivate)\s+)?struct\s+(\w+)(?:<[^>{]*>)?\s*(?::\s*([^{]+))?\{",
+    )?;
+
+    let mut structs = Vec::new();
+    for cap in struct_re.captures_iter(&code) {
+        let start = cap.get(0).unwrap().start();
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let conformances = cap
+            .get(2)
+            .map(|c| {
+                c.as_str()
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        structs.push(SwiftStruct {
+            name: cap[1].to_string(),
+            conformances,
+            description: doc_comment(&content, start),
+            source_file: path.to_path_buf(),
+            line: count_lines(&content[..start]) + 1,
+            properties: stored_properties(&content, &code, open + 1, close),
+            coding_keys: coding_keys(&code[open + 1..close]),
+        });
+    }
+
+    Ok(structs)
+}
+
+/// Stored properties declared directly in a type body
+fn stored_properties(
+    content: &str,
+    code: &str,
+    body_start: usize,
+    body_end: usize,
+) -> Vec<Property> {
+    let property_re = regex::Regex::new(
+        r"^\s*(?:@\w+(?:\([^)]*\))?\s+)*(?:(?:public|internal|private|fileprivate)(?:\(set\))?\s+)*(let|var)\s+(\w+)\s*:\s*([^={\n]+?)\s*(=|\{)?\s*$",
+    )
+    .expect("valid property regex");
+
+    let mut properties = Vec::new();
+    let mut depth = 0i32;
+    let mut offset = body_start;
+    for line in code[body_start..body_end].split_inclusive('\n') {
+        if depth == 0 && !line.contains("static ") {
+            if let Some(cap) = property_re.captures(line.trim_end()) {
+                // `var x: T { ... }` is computed, not stored
+                let computed = cap.get(4).is_some_and(|m| m.as_str() == "{");
+                if !computed {
+                    let type_name = cap[3].trim().to_string();
+                    properties.push(Property {
+                        name: cap[2].to_string(),
+                        optional: type_name.ends_with('?'),
+                        type_annotation: type_name,
+                        description: doc_comment(content, offset),
+                        readonly: &cap[1] == "let",
+                        method: false,
+                        default: None,
+                        line: count_lines(&content[..offset]) + 1,
+                    });
+                }
+            }
+        }
+        for c in line.chars() {
+            match c {
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        depth = depth.max(0);
+        offset += line.len();
+    }
+
+    properties
+}
+
+/// `enum CodingKeys: String, CodingKey { case a, b = "b_key" }` as
+/// `(property, key)` pairs
+fn coding_keys(body: &str) -> Vec<(String, String)> {
+    let enum_re = regex::Regex::new(r"\benum\s+CodingKeys\s*:[^{]*\bCodingKey\b[^{]*\{")
+        .expect("valid CodingKeys regex");
+    let case_re = regex::Regex::new(r"\bcase\s+([^\n;]+)").expect("valid case regex");
+
+    let Some(m) = enum_re.find(body) else {
+        return Vec::new();
+    };
+    let Some(close) = matching_delimiter(body, m.end() - 1) else {
+        return Vec::new();
+    };
+
+    let mut keys = Vec::new();
+    for cap in case_re.captures_iter(&body[m.end()..close]) {
+        for item in split_top_level(&cap[1], ',') {
+            let (name, raw) = match item.split_once('=') {
+                Some((name, raw)) => (name.trim(), unquote(raw.trim())),
+                None => (item.trim(), item.trim()),
+            };
+            if !name.is_empty() {
+                keys.push((name.to_string(), raw.to_string()));
+            }
+        }
+    }
+    keys
+}
+
+/// Properties that take part in coding, paired with the key their
+/// `CodingKeys` (or the synthesized conformance) uses
+pub fn coded_properties(s: &SwiftStruct) -> Vec<(&Property, String)> {
+    if s.coding_keys.is_empty() {
+        return s.properties.iter().map(|p| (p, p.name.clone())).collect();
+    }
+    s.properties
+        .iter()
+        .filter_map(|p| {
+            s.coding_keys
+                .iter()
+                .find(|(name, _)| name == &p.name)
+                .map(|(_, key)| (p, key.clone()))
+        })
+        .collect()
+}
+
+/// Text of the `///` comment lines directly above `pos`
+fn doc_comment(content: &str, pos: usize) -> Option<String> {
+    let line_start = content[..pos].rfind('\n').map_or(0, |i| i + 1);
+
+    let mut lines = Vec::new();
+    for line in content[..line_start].lines().rev() {
+        let Some(text) = line.trim().strip_prefix("///") else {
+            break;
+        };
+        lines.push(text.trim().to_string());
+    }
+    lines.reverse();
+
+    // Summary paragraph only, like the JSDoc description
+    let summary: Vec<_> = lines.into_iter().take_while(|l| !l.is_empty()).collect();
+    if summary.is_empty() {
+        None
+    } else {
+        Some(summary.join(" "))
+    }
+}
+
+/// Extract the auth API calls a Swift SDK makes.
+///
+/// Paths come from `enum *Endpoints { static let name = "path" }` constants
+/// and from `client.post("path", body: Request(...))` call sites. Body keys
+/// follow the encoder's `keyEncodingStrategy` when it converts to snake case.
+pub fn extract_endpoints(pkg_path: &Path, client: &str) -> Result<Vec<ClientEndpoint>> {
+    let files = source_files(pkg_path);
+
+    let mut sources = Vec::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        sources.push((file.clone(), content));
+    }
+
+    let snake_case = key_strategies(pkg_path)?.encode_snake_case;
+
+    let mut structs = HashMap::new();
+    for file in &files {
+        for s in extract_structs(file)? {
+            structs.entry(s.name.clone()).or_insert(s);
+        }
+    }
+
+    let endpoints_re = regex::Regex::new(r"\benum\s+(\w*Endpoints)\b[^{]*\{")?;
+    let constant_re = regex::Regex::new(r#"\bstatic\s+let\s+(\w+)\s*=\s*"([^"]*)""#)?;
+    let call_re =
+        regex::Regex::new(r"\b\w*[cC]lient\.(get|post|put|patch|delete)\s*(?:<[^>]*>)?\(")?;
+
+    // Endpoint constants first, so call sites can refer to them by name
+    let mut endpoints = Vec::new();
+    let mut constants: HashMap<String, String> = HashMap::new();
+    for (file, content) in &sources {
+        let code = blank_comments(content);
+        for cap in endpoints_re.captures_iter(&code) {
+            let open = cap.get(0).unwrap().end() - 1;
+            let Some(close) = matching_delimiter(&code, open) else {
+                continue;
+            };
+            for constant in constant_re.captures_iter(&code[open..close]) {
+                let pos = open + constant.get(0).unwrap().start();
+                constants.insert(constant[1].to_string(), constant[2].to_string());
+                endpoints.push(ClientEndpoint {
+                    client: client.to_string(),
+                    name: Some(format!("{}.{}", &cap[1], &constant[1])),
+                    method: None,
+                    path: constant[2].to_string(),
+                    body_fields: None,
+                    source_file: file.clone(),
+                    line: count_lines(&content[..pos]) + 1,
+                });
+            }
+        }
+    }
+
+    for (file, content) in &sources {
+        let code = blank_comments(content);
+        for cap in call_re.captures_iter(&code) {
+            let start = cap.get(0).unwrap().start();
+            let open = cap.get(0).unwrap().end() - 1;
+            let Some(close) = matching_delimiter(&code, open) else {
+                continue;
+            };
+            let args = split_top_level(&code[open + 1..close], ',');
+            let Some(path_arg) = args.first().map(|a| a.trim()) else {
+                continue;
+            };
+
+            let path = if path_arg.starts_with('"') {
+                unquote(path_arg).to_string()
+            } else {
+                // `AuthEndpoints.refresh` or `endpoints.refresh`
+                let name = path_arg.rsplit('.').next().unwrap_or(path_arg);
+                match constants.get(name) {
+                    Some(path) => path.clone(),
+                    None => continue,
+                }
+            };
+
+            let body_fields = args
+                .iter()
+                .find_map(|a| a.trim().strip_prefix("body:"))
+                .and_then(|body| body_struct(&code[..start], body.trim()))
+                .and_then(|name| structs.get(&name))
+                .map(|s| {
+                    coded_properties(s)
+                        .into_iter()
+                        .map(
+                            |(_, key)| {
+                                if snake_case {
+                                    to_snake_case(&key)
+                                } else {
+                                    key
+                                }
+                            },
+                        )
+                        .collect()
+                });
+
+            debug!("Found Swift call {} {}", &cap[1], path);
+
+            endpoints.push(ClientEndpoint {
+                client: client.to_string(),
+                name: None,
+                method: Some(cap[1].to_uppercase()),
+                path,
+                body_fields,
+                source_file: file.clone(),
+                line: count_lines(&content[..start]) + 1,
+            });
+        }
+    }
+
+    Ok(endpoints)
+}
+
+/// Type of a `body:` argument: `Request(...)` directly, or a local
+/// `let request = Request(...)` declared earlier in the same scope
+fn body_struct(before: &str, body: &str) -> Option<String> {
+    let init_re = regex::Regex::new(r"^([A-Z]\w*)\s*\(").expect("valid init regex");
+    if let Some(cap) = init_re.captures(body) {
+        return Some(cap[1].to_string());
+    }
+
+    let local_re = regex::Regex::new(&format!(
+        r"\blet\s+{}\s*(?::\s*(\w+))?\s*=\s*([A-Z]\w*)?",
+        regex::escape(body)
+    ))
+    .ok()?;
+    let cap = local_re.captures_iter(before).last()?;
+    cap.get(1).or(cap.get(2)).map(|m| m.as_str().to_string())
+}
+
+/// `identityToken` -> `identity_token`, as `JSONEncoder.convertToSnakeCase` does
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            // Acronyms stay together: `userID` -> `user_id`
+            let prev_lower = !chars[i - 1].is_uppercase();
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev_lower || next_lower {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// `identity_token` -> `identityToken`, as `JSONDecoder.convertFromSnakeCase` does
+pub fn from_snake_case(key: &str) -> String {
+    if !key.contains('_') {
+        return key.to_string();
+    }
+    let mut out = String::new();
+    for (i, word) in key.split('_').filter(|w| !w.is_empty()).enumerate() {
+        if i == 0 {
+            out.push_str(word);
+        } else {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                out.extend(first.to_uppercase());
+                out.push_str(chars.as_str());
+            }
+        }
+    }
+    out
+}
@@ -4,20 +4,21 @@
 //! from TypeScript source files using tree-sitter.
 
 use anyhow::{Context, Result};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 use walkdir::WalkDir;
 
+use super::{changelog, drizzle, env, fastify, mongoose, options, security, sveltekit, swift, zod};
 use crate::types::{
-    Export, ExportKind, ExtractedDocs, Package, PackageConfig, PackageKind, Parameter,
+    Export, ExportKind, ExtractedDocs, Package, PackageConfig, Parameter, PeerDependency, Property,
 };
 
 /// Extract documentation from a TypeScript package
 pub async fn extract_package(path: &Path, config: &PackageConfig) -> Result<ExtractedDocs> {
     info!("Extracting TypeScript documentation from {}", path.display());
 
-    let mut files: HashMap<std::path::PathBuf, Vec<Export>> = HashMap::new();
+    let mut files: BTreeMap<PathBuf, Vec<Export>> = BTreeMap::new();
 
     // Process each entry point
     for entry_point in &config.entry_points {
@@ -29,6 +30,7 @@ pub async fn extract_package(path: &Path, config: &PackageConfig) -> Result<Extr
     }
 
     // Also scan src directory for additional exports
+    let mut sources = Vec::new();
     let src_dir = path.join("src");
     if src_dir.exists() {
         for entry in WalkDir::new(&src_dir)
@@ -36,15 +38,17 @@ pub async fn extract_package(path: &Path, config: &PackageConfig) -> Result<Extr
             .filter_map(|e| e.ok())
             .filter(|e| {
                 let path = e.path();
-                path.extension().map_or(false, |ext| ext == "ts" || ext == "tsx")
+                path.extension()
+                    .is_some_and(|ext| ext == "ts" || ext == "tsx")
                     && !is_excluded(path, &config.exclude)
             })
         {
             let file_path = entry.path().to_path_buf();
-            if !files.contains_key(&file_path) {
-                let exports = extract_file(&file_path).await?;
+            sources.push(file_path.clone());
+            if let std::collections::btree_map::Entry::Vacant(slot) = files.entry(file_path) {
+                let exports = extract_file(slot.key()).await?;
                 if !exports.is_empty() {
-                    files.insert(file_path, exports);
+                    slot.insert(exports);
                 }
             }
         }
@@ -52,21 +56,85 @@ pub async fn extract_package(path: &Path, config: &PackageConfig) -> Result<Extr
 
     // Read package.json for metadata
     let pkg_json_path = path.join("package.json");
-    let (name, version, description) = if pkg_json_path.exists() {
+    let (name, version, description, peer_dependencies) = if pkg_json_path.exists() {
         let content = std::fs::read_to_string(&pkg_json_path)?;
         let pkg: serde_json::Value = serde_json::from_str(&content)?;
         (
             pkg["name"].as_str().unwrap_or("unknown").to_string(),
             pkg["version"].as_str().unwrap_or("0.0.0").to_string(),
             pkg["description"].as_str().unwrap_or("").to_string(),
+            peer_dependencies(&pkg),
         )
     } else {
-        ("unknown".to_string(), "0.0.0".to_string(), String::new())
+        (
+            "unknown".to_string(),
+            "0.0.0".to_string(),
+            String::new(),
+            Vec::new(),
+        )
     };
 
     // Read README if exists
     let readme = read_optional_file(&path.join("README.md"));
     let changelog = read_optional_file(&path.join("CHANGELOG.md"));
+    let releases = changelog
+        .as_deref()
+        .map(changelog::parse)
+        .unwrap_or_default();
+
+    // A Swift package is named by its manifest and versioned by releases
+    let swift = swift::extract_manifest(path)?;
+    let (name, version) = match &swift {
+        Some(manifest) if !pkg_json_path.exists() => (
+            manifest.name.clone(),
+            releases
+                .iter()
+                .find(|r| r.version != "Unreleased")
+                .map_or(version, |r| r.version.clone()),
+        ),
+        _ => (name, version),
+    };
+
+    // Mongoose schemas (MongoDB adapter)
+    let models_dir = path.join("src").join("models");
+    let models = if models_dir.exists() {
+        mongoose::extract_models(&models_dir)?
+    } else {
+        Vec::new()
+    };
+
+    // Drizzle tables (Drizzle adapter)
+    let schema_dir = path.join("src").join("schema");
+    let tables = if schema_dir.is_dir() {
+        drizzle::extract_tables(&schema_dir)?
+    } else {
+        Vec::new()
+    };
+
+    // HTTP routes and the Zod schemas validating them
+    sources.sort();
+    let routes = fastify::extract_routes(path, &sources)?;
+    let request_decorations = fastify::extract_request_decorations(&sources)?;
+    let plugins = fastify::extract_plugins(&sources)?;
+    let mut schemas = Vec::new();
+    for source in &sources {
+        schemas.extend(zod::extract_schemas(source)?);
+    }
+
+    // Configuration interfaces, flattened with their defaults
+    let exports: Vec<Export> = files.values().flatten().cloned().collect();
+    let config_options = options::extract_options(&exports, &sources)?;
+
+    // Environment variables, including those only shown in examples
+    let env_vars = env::extract_env_vars(path)?;
+
+    // Claims from `Security:` sections and `@security` tags
+    let security_notes = security::extract_notes(&sources)?;
+
+    // SvelteKit hooks, filesystem routes and `App.Locals`
+    let hooks = sveltekit::extract_hooks(&sources)?;
+    let page_routes = sveltekit::extract_routes(path)?;
+    let locals = sveltekit::extract_locals(&sources)?;
 
     Ok(ExtractedDocs {
         package: Package {
@@ -75,15 +143,59 @@ pub async fn extract_package(path: &Path, config: &PackageConfig) -> Result<Extr
             description,
             path: path.to_path_buf(),
             kind: config.kind.clone(),
-            internal_deps: Vec::new(), // TODO: Parse from package.json
-            exports: files.values().flatten().cloned().collect(),
+            // Resolved against the other packages by `deps::dependency_graph`
+            internal_deps: Vec::new(),
+            dependents: Vec::new(),
+            exports,
+            peer_dependencies,
+            swift,
+            repository: config.repository.clone(),
         },
         files,
         readme,
         changelog,
+        releases,
+        models,
+        tables,
+        routes,
+        schemas,
+        request_decorations,
+        plugins,
+        config_options,
+        env_vars,
+        security_notes,
+        hooks,
+        page_routes,
+        locals,
     })
 }
 
+/// Directories that hold build output or dependencies, never sources
+pub const SKIPPED_DIRS: [&str; 6] = [
+    "node_modules",
+    "dist",
+    "build",
+    ".svelte-kit",
+    "coverage",
+    "target",
+];
+
+/// TypeScript sources under `src`, excluding tests
+pub fn source_files(pkg_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<_> = WalkDir::new(pkg_path.join("src"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "ts"))
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            !name.contains(".test.") && !name.contains(".spec.")
+        })
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
 /// Extract exports from a single TypeScript file
 pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
     let content = std::fs::read_to_string(path)
@@ -98,14 +210,22 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
 
     // Extract exported interfaces
     let interface_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+interface\s+(\w+)(?:<[^>]+>)?\s*\{([^}]*)\}"
+        r"(?m)^export\s+interface\s+(\w+)(?:<[^>]+>)?(?:\s+extends\s+([^{]+))?\s*\{",
     )?;
+    let code = blank_comments(&content);
 
     for cap in interface_re.captures_iter(&content) {
         let name = cap[1].to_string();
-        let body = cap.get(2).map_or("", |m| m.as_str());
         let jsdoc = extract_jsdoc(&content, cap.get(0).unwrap().start());
 
+        let open = cap.get(0).unwrap().end() - 1;
+        let properties = matching_delimiter(&code, open)
+            .map(|close| {
+                let line = count_lines(&content[..open]) + 1;
+                parse_members(&content[open + 1..close], line)
+            })
+            .unwrap_or_default();
+
         exports.push(Export {
             name: name.clone(),
             kind: ExportKind::Interface,
@@ -117,13 +237,15 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: None,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties,
+            implements: Vec::new(),
+            extends: cap.get(2).map_or_else(Vec::new, |m| heritage(m.as_str())),
         });
     }
 
     // Extract exported types
-    let type_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+type\s+(\w+)(?:<[^>]+>)?\s*=\s*([^;]+);"
-    )?;
+    let type_re = regex::Regex::new(r"(?m)^export\s+type\s+(\w+)(?:<[^>]+>)?\s*=\s*([^;]+);")?;
 
     for cap in type_re.captures_iter(&content) {
         let name = cap[1].to_string();
@@ -141,12 +263,16 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: None,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties: Vec::new(),
+            implements: Vec::new(),
+            extends: Vec::new(),
         });
     }
 
     // Extract exported functions
     let fn_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+(?:async\s+)?function\s+(\w+)\s*(?:<[^>]+>)?\s*\(([^)]*)\)(?:\s*:\s*([^{]+))?\s*\{"
+        r"(?m)^export\s+(?:async\s+)?function\s+(\w+)\s*(?:<[^>]+>)?\s*\(([^)]*)\)(?:\s*:\s*([^{]+))?\s*\{",
     )?;
 
     for cap in fn_re.captures_iter(&content) {
@@ -168,13 +294,15 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: return_type,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties: Vec::new(),
+            implements: Vec::new(),
+            extends: Vec::new(),
         });
     }
 
     // Extract exported const/variables
-    let const_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+const\s+(\w+)(?:\s*:\s*([^=]+))?\s*="
-    )?;
+    let const_re = regex::Regex::new(r"(?m)^export\s+const\s+(\w+)(?:\s*:\s*([^=]+))?\s*=")?;
 
     for cap in const_re.captures_iter(&content) {
         let name = cap[1].to_string();
@@ -192,17 +320,31 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: None,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties: Vec::new(),
+            implements: Vec::new(),
+            extends: Vec::new(),
         });
     }
 
     // Extract exported classes
     let class_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+class\s+(\w+)(?:<[^>]+>)?(?:\s+extends\s+[^{]+)?(?:\s+implements\s+[^{]+)?\s*\{"
+        r"(?m)^export\s+class\s+(\w+)(?:<[^>]+>)?(?:\s+extends\s+([^{]+?))?(?:\s+implements\s+([^{]+))?\s*\{",
     )?;
 
     for cap in class_re.captures_iter(&content) {
         let name = cap[1].to_string();
         let jsdoc = extract_jsdoc(&content, cap.get(0).unwrap().start());
+        let extends = cap.get(2).map_or_else(Vec::new, |m| heritage(m.as_str()));
+        let implements = cap.get(3).map_or_else(Vec::new, |m| heritage(m.as_str()));
+
+        let open = cap.get(0).unwrap().end() - 1;
+        let methods = matching_delimiter(&code, open)
+            .map(|close| {
+                let line = count_lines(&content[..open]) + 1;
+                parse_class_methods(&content[open + 1..close], line)
+            })
+            .unwrap_or_default();
 
         exports.push(Export {
             name: name.clone(),
@@ -215,13 +357,15 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: None,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties: methods,
+            implements,
+            extends,
         });
     }
 
     // Extract exported enums
-    let enum_re = regex::Regex::new(
-        r"(?m)^(?:/\*\*[\s\S]*?\*/\s*)?export\s+(?:const\s+)?enum\s+(\w+)\s*\{"
-    )?;
+    let enum_re = regex::Regex::new(r"(?m)^export\s+(?:const\s+)?enum\s+(\w+)\s*\{")?;
 
     for cap in enum_re.captures_iter(&content) {
         let name = cap[1].to_string();
@@ -238,6 +382,10 @@ pub async fn extract_file(path: &Path) -> Result<Vec<Export>> {
             returns: None,
             examples: jsdoc.examples,
             deprecated: jsdoc.deprecated,
+            stability: jsdoc.stability,
+            properties: Vec::new(),
+            implements: Vec::new(),
+            extends: Vec::new(),
         });
     }
 
@@ -329,26 +477,45 @@ pub async fn extract_to_markdown(source: &str, output: &str) -> Result<()> {
 
 // Helper types and functions
 
-struct JsDoc {
-    description: Option<String>,
-    params: HashMap<String, String>,
-    returns: Option<String>,
-    examples: Vec<String>,
-    deprecated: Option<String>,
+/// Parsed JSDoc comment
+pub struct JsDoc {
+    pub description: Option<String>,
+    pub params: HashMap<String, String>,
+    pub returns: Option<String>,
+    pub examples: Vec<String>,
+    pub deprecated: Option<String>,
+    pub stability: Option<String>,
+    pub default_value: Option<String>,
 }
 
-fn extract_jsdoc(content: &str, export_start: usize) -> JsDoc {
+/// Type names in an `extends` or `implements` clause, without type arguments
+fn heritage(clause: &str) -> Vec<String> {
+    split_top_level(clause, ',')
+        .iter()
+        .map(|i| i.split('<').next().unwrap_or_default().trim().to_string())
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+/// Parse the JSDoc comment preceding `export_start`
+pub fn extract_jsdoc(content: &str, export_start: usize) -> JsDoc {
     let mut jsdoc = JsDoc {
         description: None,
         params: HashMap::new(),
         returns: None,
         examples: Vec::new(),
         deprecated: None,
+        stability: None,
+        default_value: None,
     };
 
     // Find JSDoc comment before export
     let before = &content[..export_start];
     if let Some(comment_end) = before.rfind("*/") {
+        // Only a comment directly above the export documents it
+        if !before[comment_end + 2..].trim().is_empty() {
+            return jsdoc;
+        }
         if let Some(comment_start) = before[..comment_end].rfind("/**") {
             let comment = &before[comment_start + 3..comment_end];
 
@@ -358,10 +525,18 @@ fn extract_jsdoc(content: &str, export_start: usize) -> JsDoc {
             let mut current_example = String::new();
 
             for line in comment.lines() {
-                let line = line.trim().trim_start_matches('*').trim();
+                let mut line = line.trim().trim_start_matches('*').trim();
+
+                // `/** Cookie name @default 'x' */` puts the tag after the text
+                if let Some(at) = line.find(" @default").filter(|_| !line.starts_with('@')) {
+                    jsdoc.default_value = parse_default_tag(&line[at + 1..]);
+                    line = line[..at].trim_end();
+                }
 
-                if line.starts_with("@param") {
-                    let parts: Vec<&str> = line[6..].trim().splitn(2, ' ').collect();
+                if let Some(value) = parse_default_tag(line) {
+                    jsdoc.default_value = Some(value);
+                } else if let Some(rest) = line.strip_prefix("@param") {
+                    let parts: Vec<&str> = rest.trim().splitn(2, ' ').collect();
                     if parts.len() >= 2 {
                         jsdoc.params.insert(
                             parts[0].trim_start_matches('{').trim_end_matches('}').to_string(),
@@ -370,11 +545,26 @@ fn extract_jsdoc(content: &str, export_start: usize) -> JsDoc {
                     }
                 } else if line.starts_with("@returns") || line.starts_with("@return") {
                     jsdoc.returns = Some(line[8..].trim().to_string());
-                } else if line.starts_with("@example") {
+                } else if let Some(rest) = line.strip_prefix("@example") {
+                    if in_example && !current_example.is_empty() {
+                        jsdoc.examples.push(current_example.trim().to_string());
+                        current_example.clear();
+                    }
                     in_example = true;
-                } else if line.starts_with("@deprecated") {
-                    jsdoc.deprecated = Some(line[11..].trim().to_string());
+                    // `@example {@include name}` names a marked region inline
+                    if rest.trim_start().starts_with("{@include") {
+                        current_example.push_str(rest.trim());
+                        current_example.push('\n');
+                    }
+                } else if let Some(rest) = line.strip_prefix("@deprecated") {
+                    jsdoc.deprecated = Some(rest.trim().to_string());
                 } else if line.starts_with('@') {
+                    if let Some(tag) = ["@alpha", "@beta", "@experimental"]
+                        .into_iter()
+                        .find(|tag| line == *tag)
+                    {
+                        jsdoc.stability = Some(tag[1..].to_string());
+                    }
                     if in_example && !current_example.is_empty() {
                         jsdoc.examples.push(current_example.trim().to_string());
                         current_example.clear();
@@ -401,6 +591,200 @@ fn extract_jsdoc(content: &str, export_start: usize) -> JsDoc {
     jsdoc
 }
 
+/// Value of a `@defaultValue` or `@default` tag line, without backticks
+fn parse_default_tag(line: &str) -> Option<String> {
+    let rest = line
+        .strip_prefix("@defaultValue")
+        .or_else(|| line.strip_prefix("@default"))?;
+    if rest.starts_with(|c: char| c.is_alphanumeric()) {
+        return None;
+    }
+    let value = rest.trim().trim_matches('`').trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Parse the members of an interface or object type body.
+///
+/// `first_line` is the source line of the opening brace, used to give each
+/// member an absolute line number.
+pub fn parse_members(body: &str, first_line: usize) -> Vec<Property> {
+    let code = blank_comments(body);
+    let member_re =
+        regex::Regex::new(r#"^\s*(readonly\s+)?([A-Za-z_$][\w$]*|'[^']+'|"[^"]+")(\?)?\s*([:(<])"#)
+            .expect("valid member regex");
+
+    // Member starts: lines that begin a declaration at nesting depth 0
+    let mut starts = Vec::new();
+    let mut depth = 0i32;
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        if depth == 0 {
+            if let Some(cap) = member_re.captures(line) {
+                let start = offset + cap.get(1).or(cap.get(2)).unwrap().start();
+                starts.push((start, cap));
+            }
+        }
+        let mut prev = ' ';
+        for c in line.chars() {
+            match c {
+                '{' | '[' | '(' | '<' => depth += 1,
+                '}' | ']' | ')' => depth -= 1,
+                '>' if prev != '=' => depth -= 1,
+                _ => {}
+            }
+            prev = c;
+        }
+        depth = depth.max(0);
+        offset += line.len();
+    }
+
+    let mut members = Vec::new();
+    let mut prev_end = 0;
+    for (i, (start, cap)) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).map_or(code.len(), |(next, _)| *next);
+        let text = code[*start..end].trim_end();
+        let text = text.trim_end_matches([';', ',']).trim_end();
+
+        let name = unquote(&cap[2]).to_string();
+        let method = &cap[4] != ":";
+        let rest = &text[text.find(&cap[2]).unwrap_or(0) + cap[2].len()..];
+        let rest = rest.trim_start().trim_start_matches('?').trim_start();
+        let type_annotation = if method {
+            rest.to_string()
+        } else {
+            rest.trim_start_matches(':').trim().to_string()
+        };
+
+        // The member's own JSDoc sits between the previous member and this one
+        let jsdoc = extract_jsdoc(&body[prev_end..*start], *start - prev_end);
+
+        members.push(Property {
+            name,
+            type_annotation: collapse_whitespace(&type_annotation),
+            description: jsdoc.description,
+            optional: cap.get(3).is_some(),
+            readonly: cap.get(1).is_some(),
+            method,
+            default: jsdoc.default_value,
+            line: first_line + count_lines(&body[..*start]),
+        });
+
+        prev_end = *start + text.len();
+    }
+
+    members
+}
+
+/// Parse the public methods of a class body.
+///
+/// Each method's `type_annotation` is its call signature, written like an
+/// interface method (`(id: string): Promise<User>`) so the two compare
+/// directly. Constructors, accessors and private members are skipped.
+pub fn parse_class_methods(body: &str, first_line: usize) -> Vec<Property> {
+    let code = blank_comments(body);
+    let method_re = regex::Regex::new(
+        r"^\s*((?:(?:public|private|protected|static|override|async)\s+)*)([A-Za-z_$][\w$]*)(\?)?\s*(?:<[^>(]*>)?\s*\(",
+    )
+    .expect("valid method regex");
+
+    let mut methods = Vec::new();
+    let mut depth = 0i32;
+    let mut offset = 0;
+    let mut prev_end = 0;
+    for line in code.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        if depth == 0 && line_start >= prev_end {
+            if let Some(cap) = method_re.captures(line) {
+                let modifiers = &cap[1];
+                let name = cap[2].to_string();
+                let skip = name == "constructor"
+                    || modifiers.contains("private")
+                    || modifiers.contains("protected")
+                    || modifiers.contains("static");
+
+                let start = line_start + cap.get(1).unwrap().start();
+                let params_open = line_start + cap.get(0).unwrap().end() - 1;
+                let Some(params_close) = matching_delimiter(&code, params_open) else {
+                    continue;
+                };
+                let Some(body_open) = method_body_start(&code, params_close + 1) else {
+                    continue;
+                };
+                let end = matching_delimiter(&code, body_open).map_or(code.len(), |c| c + 1);
+
+                if !skip {
+                    let returns = code[params_close + 1..body_open]
+                        .trim()
+                        .trim_start_matches(':')
+                        .trim();
+                    let name_end = line_start + cap.get(2).unwrap().end();
+                    let mut signature = code[name_end..=params_close]
+                        .trim_start_matches('?')
+                        .trim()
+                        .to_string();
+                    if !returns.is_empty() {
+                        signature.push_str(": ");
+                        signature.push_str(returns);
+                    }
+
+                    let jsdoc = extract_jsdoc(&body[prev_end..start], start - prev_end);
+
+                    methods.push(Property {
+                        name,
+                        type_annotation: collapse_whitespace(&signature),
+                        description: jsdoc.description,
+                        optional: cap.get(3).is_some(),
+                        readonly: false,
+                        method: true,
+                        default: None,
+                        line: first_line + count_lines(&body[..start]),
+                    });
+                }
+
+                prev_end = end;
+                continue;
+            }
+        }
+
+        if line_start < prev_end {
+            continue;
+        }
+        for c in line.chars() {
+            match c {
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        depth = depth.max(0);
+    }
+
+    methods
+}
+
+/// Position of the `{` opening a method body, skipping the return type
+fn method_body_start(code: &str, from: usize) -> Option<usize> {
+    let mut angle = 0i32;
+    let mut prev = ' ';
+    for (i, c) in code[from..].char_indices() {
+        match c {
+            '<' => angle += 1,
+            '>' if prev != '=' => angle -= 1,
+            '{' if angle <= 0 => return Some(from + i),
+            ';' if angle <= 0 => return None,
+            _ => {}
+        }
+        prev = c;
+    }
+    None
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 fn parse_function_params(params_str: &str, jsdoc: &JsDoc) -> Vec<Parameter> {
     let mut params = Vec::new();
 
@@ -436,10 +820,192 @@ fn parse_function_params(params_str: &str, jsdoc: &JsDoc) -> Vec<Parameter> {
     params
 }
 
-fn count_lines(s: &str) -> usize {
+/// Count newlines in a slice (used to turn byte offsets into line numbers)
+pub fn count_lines(s: &str) -> usize {
     s.chars().filter(|&c| c == '\n').count()
 }
 
+/// Find the byte index of the delimiter closing the one at `open`.
+///
+/// Understands `{}`, `[]`, `()` and `<>` pairs and skips string literals,
+/// so braces inside quoted text do not confuse the match.
+pub fn matching_delimiter(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let (open_ch, close_ch) = match bytes.get(open)? {
+        b'{' => (b'{', b'}'),
+        b'[' => (b'[', b']'),
+        b'(' => (b'(', b')'),
+        b'<' => (b'<', b'>'),
+        _ => return None,
+    };
+
+    let mut depth = 0usize;
+    let mut quote: Option<u8> = None;
+    let mut i = open;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if let Some(q) = quote {
+            if c == b'\\' {
+                i += 1;
+            } else if c == q {
+                quote = None;
+            }
+        } else if c == b'\'' || c == b'"' || c == b'`' {
+            quote = Some(c);
+        } else if c == open_ch {
+            depth += 1;
+        } else if c == close_ch {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Blank out `//` and `/* */` comments, leaving string literals intact.
+///
+/// Comment characters are replaced with spaces of the same byte length, so
+/// offsets into the result are valid offsets into the original source.
+pub fn blank_comments(content: &str) -> String {
+    fn blank(out: &mut String, c: char) {
+        if c == '\n' {
+            out.push('\n');
+        } else {
+            out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        }
+    }
+
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                blank(&mut out, c);
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    blank(&mut out, next);
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                blank(&mut out, c);
+                chars.next();
+                blank(&mut out, '*');
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    blank(&mut out, next);
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            ('\'' | '"' | '`', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Split the body of an object literal into top-level `key: value` pairs.
+///
+/// `body` is the text between the outer braces. Shorthand properties
+/// (`{ handler }`) are returned with the key repeated as the value, and
+/// spreads are skipped. Quotes around keys are removed.
+pub fn object_entries(body: &str) -> Vec<(String, String)> {
+    split_top_level(body, ',')
+        .into_iter()
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            if entry.is_empty() || entry.starts_with("...") {
+                return None;
+            }
+            match split_top_level(entry, ':').split_first() {
+                Some((key, rest)) if !rest.is_empty() => Some((
+                    unquote(key.trim()).to_string(),
+                    rest.join(":").trim().to_string(),
+                )),
+                _ => Some((entry.to_string(), entry.to_string())),
+            }
+        })
+        .collect()
+}
+
+/// Split `s` on `sep` wherever it appears outside brackets and strings
+pub fn split_top_level(s: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+
+    for c in s.chars() {
+        if let Some(q) = quote {
+            if c == q && prev != '\\' {
+                quote = None;
+            }
+        } else {
+            match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' => depth -= 1,
+                // `=>` is an arrow, not a closing generic
+                '<' => depth += 1,
+                '>' if prev != '=' && depth > 0 => depth -= 1,
+                _ if c == sep && depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    prev = c;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        current.push(c);
+        prev = c;
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Strip one layer of matching quotes from a string literal
+pub fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    for q in ['\'', '"', '`'] {
+        if s.len() >= 2 && s.starts_with(q) && s.ends_with(q) {
+            return &s[1..s.len() - 1];
+        }
+    }
+    s
+}
+
 fn is_excluded(path: &Path, patterns: &[String]) -> bool {
     let path_str = path.to_string_lossy();
     for pattern in patterns {
@@ -452,6 +1018,24 @@ fn is_excluded(path: &Path, patterns: &[String]) -> bool {
     false
 }
 
+/// `peerDependencies` of a package.json, optional if `peerDependenciesMeta`
+/// says so
+fn peer_dependencies(pkg: &serde_json::Value) -> Vec<PeerDependency> {
+    let Some(peers) = pkg["peerDependencies"].as_object() else {
+        return Vec::new();
+    };
+    peers
+        .iter()
+        .map(|(name, version)| PeerDependency {
+            name: name.clone(),
+            version: version.as_str().unwrap_or("*").to_string(),
+            optional: pkg["peerDependenciesMeta"][name]["optional"]
+                .as_bool()
+                .unwrap_or(false),
+        })
+        .collect()
+}
+
 fn read_optional_file(path: &Path) -> Option<String> {
     std::fs::read_to_string(path).ok()
 }
@@ -0,0 +1,148 @@
+//! Zod schema extractor
+//!
+//! Reads exported `z.object(...)`/`strictObject(...)` validation schemas so
+//! request bodies and parameters can be documented field by field.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::debug;
+
+use super::typescript::{
+    blank_comments, count_lines, extract_jsdoc, matching_delimiter, object_entries,
+    split_top_level, unquote,
+};
+use crate::types::{ZodField, ZodSchema};
+
+/// Zod calls that wrap another schema rather than define a type
+const WRAPPERS: [&str; 4] = ["preprocess", "coerce", "lazy", "effect"];
+
+/// Refinements worth documenting
+const CHECKS: [&str; 16] = [
+    "min",
+    "max",
+    "length",
+    "regex",
+    "email",
+    "uuid",
+    "url",
+    "int",
+    "positive",
+    "nonnegative",
+    "datetime",
+    "default",
+    "nullable",
+    "trim",
+    "toLowerCase",
+    "toUpperCase",
+];
+
+/// Extract exported object schemas from a file
+pub fn extract_schemas(path: &Path) -> Result<Vec<ZodSchema>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let code = blank_comments(&content);
+
+    debug!("Extracting Zod schemas from {}", path.display());
+
+    let schema_re = regex::Regex::new(
+        r"(?m)^export\s+const\s+(\w+)\s*=\s*(strictObject|z\.object|z\.strictObject)\(",
+    )?;
+
+    let call_re = regex::Regex::new(r"\.(\w+)\(")?;
+
+    let mut schemas = Vec::new();
+
+    for cap in schema_re.captures_iter(&code) {
+        let start = cap.get(0).unwrap().start();
+        let open = cap.get(0).unwrap().end() - 1;
+        let Some(close) = matching_delimiter(&code, open) else {
+            continue;
+        };
+
+        let args = &code[open + 1..close];
+        let fields = match args.find('{') {
+            Some(obj) => match matching_delimiter(args, obj) {
+                Some(obj_end) => object_entries(&args[obj + 1..obj_end])
+                    .into_iter()
+                    .map(|(name, value)| parse_field(&call_re, name, &value))
+                    .collect(),
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+
+        let chained = code[close + 1..].split(';').next().unwrap_or("");
+        let strict = &cap[2] != "z.object" || chained.contains(".strict()");
+
+        let jsdoc = extract_jsdoc(&content, start);
+
+        schemas.push(ZodSchema {
+            name: cap[1].to_string(),
+            description: jsdoc.description,
+            source_file: path.to_path_buf(),
+            line: count_lines(&content[..start]) + 1,
+            strict,
+            fields,
+        });
+    }
+
+    Ok(schemas)
+}
+
+/// Parse a single `name: z.string().min(1)...` property
+fn parse_field(call_re: &regex::Regex, name: String, value: &str) -> ZodField {
+    let mut type_name = "unknown".to_string();
+    let mut checks = Vec::new();
+    let mut optional = false;
+
+    for cap in call_re.captures_iter(value) {
+        let method = &cap[1];
+        let open = cap.get(0).unwrap().end() - 1;
+        let args = matching_delimiter(value, open)
+            .map(|close| &value[open + 1..close])
+            .unwrap_or("");
+        let first_arg = split_top_level(args, ',')
+            .into_iter()
+            .next()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+
+        // The first non-wrapper `z.xxx(` is the base type
+        let receiver = &value[..cap.get(0).unwrap().start()];
+        if receiver.ends_with('z') && !receiver.ends_with(".z") {
+            if type_name == "unknown" && !WRAPPERS.contains(&method) {
+                type_name = method.to_string();
+                if method == "enum" {
+                    checks.push(("enum".to_string(), first_arg));
+                }
+            }
+            continue;
+        }
+
+        match method {
+            "optional" | "nullish" => optional = true,
+            "default" => {
+                optional = true;
+                checks.push((method.to_string(), first_arg));
+            }
+            // String arguments on validators are error messages, not values
+            _ if CHECKS.contains(&method) => {
+                let value = first_arg.filter(|a| !is_string_literal(a));
+                checks.push((method.to_string(), value));
+            }
+            _ => {}
+        }
+    }
+
+    ZodField {
+        name: unquote(&name).to_string(),
+        type_name,
+        optional,
+        checks,
+    }
+}
+
+fn is_string_literal(s: &str) -> bool {
+    let s = s.trim();
+    s.starts_with('\'') || s.starts_with('"') || s.starts_with('`')
+}
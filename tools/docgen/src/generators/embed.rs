@@ -0,0 +1,262 @@
+//! Generated regions in hand-written pages
+//!
+//! Curated pages such as `docs/api/types.md` keep their prose but embed
+//! tables that must follow the source. The lines between
+//! `<!-- docgen:begin symbol=AuthUser view=properties -->` and
+//! `<!-- docgen:end -->` are rewritten from the extracted exports with the
+//! `region.md` template. The views are `full` (the default), `properties`,
+//! `params` and `signature`; `package=` picks the package when several
+//! export the same name.
+//!
+//! `<!-- docgen:begin changelog -->` lists the releases of every package,
+//! for a site that keeps its own changelog page.
+//!
+//! `example=<name>` regions hold an example region's code instead. A
+//! `<!-- docgen:example <name> -->` marker on a line of its own is turned
+//! into such a region, so it is refreshed on every run rather than
+//! expanded once.
+
+use anyhow::{Context as _, Result};
+use std::path::Path;
+use tera::Context;
+use walkdir::WalkDir;
+
+use super::links::SourceLinks;
+use super::markdown;
+use super::output::Output;
+use super::templates::Templates;
+use crate::extractors::examples;
+use crate::types::{ExampleRegion, Export, ExtractedDocs, IssueSeverity, ValidationIssue};
+
+/// Views a region can render
+const VIEWS: [&str; 4] = ["full", "properties", "params", "signature"];
+
+/// What regions are rendered from
+#[derive(Clone, Copy)]
+struct Sources<'a> {
+    docs: &'a [ExtractedDocs],
+    regions: &'a [ExampleRegion],
+
+    /// Relative link from the page to the generated `api` directory
+    api_dir: &'a str,
+}
+
+/// Refresh the regions of every markdown page under the output directory,
+/// returning the problems found in the markers
+pub fn update(
+    docs: &[ExtractedDocs],
+    regions: &[ExampleRegion],
+    templates: &Templates,
+    links: &SourceLinks,
+    out: &mut Output,
+) -> Result<Vec<ValidationIssue>> {
+    let mut files: Vec<_> = WalkDir::new(out.dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut problems = Vec::new();
+    for path in files {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if !content.contains("docgen:begin") && !content.contains("docgen:example") {
+            continue;
+        }
+
+        let page_dir = path.parent().unwrap_or(out.dir());
+        let links = links.for_page(page_dir);
+        let depth = page_dir
+            .strip_prefix(out.dir())
+            .map_or(0, |p| p.components().count());
+        let api_dir = match depth {
+            0 => "./api/".to_string(),
+            depth => format!("{}api/", "../".repeat(depth)),
+        };
+        let sources = Sources {
+            docs,
+            regions,
+            api_dir: &api_dir,
+        };
+        let updated = render_regions(&path, &content, sources, templates, &links, &mut problems)?;
+        out.update(&path, &updated)?;
+    }
+
+    Ok(problems)
+}
+
+/// `content` with every region rendered afresh. Regions that cannot be
+/// rendered are kept as they are and reported.
+fn render_regions(
+    path: &Path,
+    content: &str,
+    sources: Sources,
+    templates: &Templates,
+    links: &SourceLinks,
+    problems: &mut Vec<ValidationIssue>,
+) -> Result<String> {
+    let begin_re = regex::Regex::new(r"^\s*<!--\s*docgen:begin\b(.*?)-->\s*$")?;
+    let end_re = regex::Regex::new(r"^\s*<!--\s*docgen:end\s*-->\s*$")?;
+    let marker_re = regex::Regex::new(r"^\s*<!--\s*docgen:example\s+([\w./-]+)\s*-->\s*$")?;
+    let mut problem = |message: String, line: usize| {
+        problems.push(ValidationIssue {
+            severity: IssueSeverity::Error,
+            message,
+            file: Some(path.to_path_buf()),
+            line: Some(line),
+            suggestion: None,
+        })
+    };
+
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut out = String::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+
+        // A bare example marker becomes a region; unknown names are left
+        // for `docgen validate` to report
+        if let Some(cap) = marker_re.captures(line.trim_end()) {
+            if let Some(code) = examples::fenced(sources.regions, &cap[1]) {
+                out.push_str(&format!(
+                    "<!-- docgen:begin example={} -->\n\n{}\n\n<!-- docgen:end -->\n",
+                    &cap[1], code
+                ));
+                continue;
+            }
+        }
+        out.push_str(line);
+
+        let Some(cap) = begin_re.captures(line.trim_end()) else {
+            if end_re.is_match(line.trim_end()) {
+                problem(
+                    "`docgen:end` without a matching `docgen:begin`".to_string(),
+                    index,
+                );
+            }
+            continue;
+        };
+        let Some(end) = lines[index..]
+            .iter()
+            .position(|l| end_re.is_match(l.trim_end()))
+            .map(|i| index + i)
+        else {
+            problem(
+                "Generated region is never closed with `docgen:end`".to_string(),
+                index,
+            );
+            continue;
+        };
+
+        match render_region(&cap[1], sources, templates, links) {
+            Ok(body) => {
+                if !line.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push('\n');
+                out.push_str(body.trim());
+                out.push_str("\n\n");
+            }
+            Err(message) => {
+                problem(message, index);
+                lines[index..end].iter().for_each(|l| out.push_str(l));
+            }
+        }
+        out.push_str(lines[end]);
+        index = end + 1;
+    }
+
+    Ok(out)
+}
+
+/// Markdown for the region with the marker attributes `attributes`
+fn render_region(
+    attributes: &str,
+    sources: Sources,
+    templates: &Templates,
+    links: &SourceLinks,
+) -> std::result::Result<String, String> {
+    if attributes.trim() == "changelog" {
+        return Ok(markdown::merged_releases(sources.docs, sources.api_dir));
+    }
+
+    let attribute_re =
+        regex::Regex::new(r#"(\w+)=(?:"([^"]*)"|(\S+))"#).expect("valid attribute regex");
+    let mut symbol = None;
+    let mut view = "full";
+    let mut package = None;
+    let mut example = None;
+    for cap in attribute_re.captures_iter(attributes) {
+        let value = cap.get(2).or(cap.get(3)).map_or("", |m| m.as_str());
+        match &cap[1] {
+            "symbol" => symbol = Some(value),
+            "view" => view = value,
+            "package" => package = Some(value),
+            "example" => example = Some(value),
+            other => return Err(format!("Unknown region attribute `{}`", other)),
+        }
+    }
+
+    if let Some(name) = example {
+        if symbol.is_some() {
+            return Err("Generated region has both `symbol=` and `example=`".to_string());
+        }
+        return examples::fenced(sources.regions, name)
+            .ok_or_else(|| format!("Unknown example region `{}`", name));
+    }
+
+    let symbol = symbol.ok_or("Generated region has no `symbol=` or `example=`")?;
+    if !VIEWS.contains(&view) {
+        return Err(format!(
+            "Unknown view `{}` for `{}`, expected one of {}",
+            view,
+            symbol,
+            VIEWS.join(", ")
+        ));
+    }
+    let export = find_export(sources.docs, symbol, package)?;
+
+    let mut context = Context::new();
+    context.insert("export", export);
+    context.insert("view", view);
+    context.insert("source", &links.url(&export.source_file, export.line));
+    templates
+        .render("region.md", &context)
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// The export named `symbol`, from `package` or else the first package
+/// (in config order) that has one
+fn find_export<'a>(
+    docs: &'a [ExtractedDocs],
+    symbol: &str,
+    package: Option<&str>,
+) -> std::result::Result<&'a Export, String> {
+    let packages: Vec<_> = docs
+        .iter()
+        .filter(|d| {
+            package
+                .is_none_or(|p| d.package.name == p || d.package.name.ends_with(&format!("/{}", p)))
+        })
+        .collect();
+    if let (Some(package), true) = (package, packages.is_empty()) {
+        return Err(format!("Unknown package `{}`", package));
+    }
+
+    packages
+        .iter()
+        .find_map(|d| {
+            d.package
+                .exports
+                .iter()
+                .filter(|e| e.name == symbol)
+                .min_by_key(|e| (&e.source_file, e.line))
+        })
+        .ok_or_else(|| match package {
+            Some(package) => format!("Unknown symbol `{}` in {}", symbol, package),
+            None => format!("Unknown symbol `{}`", symbol),
+        })
+}
@@ -0,0 +1,162 @@
+//! Links from generated pages to the documented source
+//!
+//! Extracted symbols carry the path docgen read them from, which is
+//! meaningless on the published site. Source links point at the
+//! repository instead: `<repo_url>/blob/<ref>/<path>#L<line>`, with
+//! `repo_url` from `docgen.yaml` or `mkdocs.yml` and the ref being the tag
+//! or commit checked out. `source_ref` in `docgen.yaml` links a fixed
+//! branch or tag instead, for pages that should not change with every
+//! commit. Without either, they fall back to a path relative to the page.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use tracing::{debug, warn};
+
+use crate::types::DocgenConfig;
+
+/// Resolves source locations to link targets for one page directory
+#[derive(Debug, Clone)]
+pub struct SourceLinks {
+    /// Top level of the repository checkout
+    repo_root: PathBuf,
+
+    /// Repository URL, from the config or `mkdocs.yml`
+    repo_url: Option<String>,
+
+    /// `<repo_url>/blob/<ref>`, when both are known
+    blob_base: Option<String>,
+
+    /// Directory of the page the links are written to
+    page_dir: PathBuf,
+}
+
+impl SourceLinks {
+    /// Find the repository URL and checked out ref for `root`
+    pub fn discover(root: &Path, config: &DocgenConfig) -> Self {
+        let repo_url = config
+            .repo_url
+            .clone()
+            .or_else(|| mkdocs_repo_url(root))
+            .map(|url| url.trim_end_matches('/').to_string());
+
+        let repo_root = git(root, &["rev-parse", "--show-toplevel"])
+            .map(PathBuf::from)
+            .unwrap_or_else(|| absolute(root));
+        // A release tag reads better than a hash and is just as permanent
+        let git_ref = config.source_ref.clone().or_else(|| {
+            git(root, &["describe", "--tags", "--exact-match", "HEAD"])
+                .or_else(|| git(root, &["rev-parse", "HEAD"]))
+        });
+
+        let blob_base = match (&repo_url, git_ref) {
+            (Some(url), Some(git_ref)) => Some(format!("{}/blob/{}", url, git_ref)),
+            (None, _) => {
+                debug!("No repo_url configured, linking sources by relative path");
+                None
+            }
+            (Some(_), None) => {
+                warn!("Not a git checkout, linking sources by relative path");
+                None
+            }
+        };
+
+        Self {
+            repo_root,
+            repo_url,
+            blob_base,
+            page_dir: absolute(root),
+        }
+    }
+
+    /// The same links, written from pages in `page_dir`
+    pub fn for_page(&self, page_dir: &Path) -> Self {
+        Self {
+            page_dir: absolute(page_dir),
+            ..self.clone()
+        }
+    }
+
+    /// Repository URL, when configured
+    pub fn repo_url(&self) -> Option<&str> {
+        self.repo_url.as_deref()
+    }
+
+    /// Path of `file` in the repository, `""` for its top level
+    pub fn repo_path(&self, file: &Path) -> Option<String> {
+        let canonical = file.canonicalize().unwrap_or_else(|_| absolute(file));
+        canonical.strip_prefix(&self.repo_root).ok().map(url_path)
+    }
+
+    /// Link target for `line` of `file`
+    pub fn url(&self, file: &Path, line: usize) -> String {
+        if let (Some(base), Some(path)) = (&self.blob_base, self.repo_path(file)) {
+            return format!("{}/{}#L{}", base, path, line);
+        }
+        format!(
+            "{}#L{}",
+            url_path(&relative(&absolute(file), &self.page_dir)),
+            line
+        )
+    }
+}
+
+/// `repo_url` of the `mkdocs.yml` next to `root`, read without parsing the
+/// file, which holds `!!python/name:` tags
+fn mkdocs_repo_url(root: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(root.join("mkdocs.yml")).ok()?;
+    let re = regex::Regex::new(r#"(?m)^repo_url:\s*['"]?([^'"\s#]+)"#).ok()?;
+    re.captures(&content).map(|cap| cap[1].to_string())
+}
+
+/// Trimmed stdout of a successful git command run in `dir`
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string()).filter(|s| output.status.success() && !s.is_empty())
+}
+
+/// `path` made absolute with `.` and `..` resolved, without touching the
+/// filesystem
+pub fn absolute(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// `path` relative to the directory `from`, both absolute
+fn relative(path: &Path, from: &Path) -> PathBuf {
+    let path: Vec<_> = path.components().collect();
+    let from: Vec<_> = from.components().collect();
+    let common = path.iter().zip(&from).take_while(|(a, b)| a == b).count();
+
+    let mut out = PathBuf::new();
+    for _ in common..from.len() {
+        out.push("..");
+    }
+    for component in &path[common..] {
+        out.push(component);
+    }
+    out
+}
+
+/// Path with `/` separators, as used in URLs
+fn url_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -1,296 +1,1470 @@
 //! Markdown documentation generator
 
 use anyhow::Result;
-use std::path::Path;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tera::Context;
 
-use crate::types::{DocgenConfig, Export, ExportKind, ExtractedDocs, PackageKind};
+use super::links::SourceLinks;
+use super::mermaid;
+use super::output::Output;
+use super::templates::Templates;
+use crate::types::{
+    ConfigOption, Conformance, DependencyGraph, DocgenConfig, Export, ExportKind, ExtractedDocs,
+    IssueSeverity, MethodStatus, ModelComparison, MongooseModel, OutputConfig, PackageKind,
+    Property, Release, Route, ZodSchema,
+};
+
+/// Pages written for every package, which symbol pages must not replace
+const PACKAGE_PAGES: [&str; 9] = [
+    "index",
+    "types",
+    "functions",
+    "endpoints",
+    "collections",
+    "environment",
+    "sveltekit",
+    "changelog",
+    "config",
+];
 
 /// Generate documentation for a package
-pub async fn generate_package_docs(output_dir: &Path, docs: &ExtractedDocs) -> Result<()> {
-    std::fs::create_dir_all(output_dir)?;
+pub async fn generate_package_docs(
+    output_dir: &Path,
+    docs: &ExtractedDocs,
+    templates: &Templates,
+    options: &OutputConfig,
+    links: &SourceLinks,
+    out: &mut Output,
+) -> Result<()> {
+    let per_symbol = options.per_symbol;
+    let links = links.for_page(output_dir);
+    let symbols = symbol_pages(docs, &links);
 
     // Generate index.md for package
     let index_path = output_dir.join("index.md");
-    let index_content = generate_package_index(docs)?;
-    std::fs::write(&index_path, index_content)?;
-    info!("Generated {}", index_path.display());
+    let index_content = generate_package_index(docs, templates, &links)?;
+    out.write(&index_path, &index_content)?;
 
     // Generate types.md
     if !docs.package.exports.is_empty() {
         let types_path = output_dir.join("types.md");
-        let types_content = generate_types_doc(docs)?;
-        std::fs::write(&types_path, types_content)?;
-        info!("Generated {}", types_path.display());
+        let class_diagram = if options.diagrams.enabled {
+            mermaid::class_diagram(docs, options.diagrams.max_types)
+        } else {
+            None
+        };
+        let types_content =
+            generate_types_doc(docs, &symbols, per_symbol, class_diagram, templates)?;
+        out.write(&types_path, &types_content)?;
     }
 
     // Generate functions.md if there are functions
-    let functions: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Function)
-        .collect();
-
-    if !functions.is_empty() {
+    if docs
+        .package
+        .exports
+        .iter()
+        .any(|e| e.kind == ExportKind::Function)
+    {
         let functions_path = output_dir.join("functions.md");
-        let functions_content = generate_functions_doc(&functions, &docs.package.name)?;
-        std::fs::write(&functions_path, functions_content)?;
-        info!("Generated {}", functions_path.display());
+        let functions_content = generate_functions_doc(docs, &symbols, per_symbol, templates)?;
+        out.write(&functions_path, &functions_content)?;
+    }
+
+    // Generate <symbol>.md for every export
+    if per_symbol {
+        for symbol in &symbols {
+            let symbol_path = output_dir.join(format!("{}.md", symbol.slug));
+            let symbol_content = generate_symbol_doc(docs, symbol, templates)?;
+            out.write(&symbol_path, &symbol_content)?;
+        }
+    }
+
+    // Generate endpoints.md for HTTP routes
+    if !docs.routes.is_empty() {
+        let endpoints_path = output_dir.join("endpoints.md");
+        let endpoints_content = generate_endpoints_doc(docs, &links)?;
+        out.write(&endpoints_path, &endpoints_content)?;
+    }
+
+    // Generate collections.md for Mongoose models
+    if !docs.models.is_empty() {
+        let collections_path = output_dir.join("collections.md");
+        let collections_content = generate_collections_doc(docs, &links)?;
+        out.write(&collections_path, &collections_content)?;
+    }
+
+    // Generate environment.md for environment variables
+    if !docs.env_vars.is_empty() {
+        let environment_path = output_dir.join("environment.md");
+        let environment_content = generate_environment_doc(docs, &links)?;
+        out.write(&environment_path, &environment_content)?;
+    }
+
+    // Generate sveltekit.md for hooks, routes and locals
+    if !docs.hooks.is_empty() || !docs.page_routes.is_empty() || !docs.locals.is_empty() {
+        let sveltekit_path = output_dir.join("sveltekit.md");
+        let sveltekit_content = generate_sveltekit_doc(docs, &links)?;
+        out.write(&sveltekit_path, &sveltekit_content)?;
+    }
+
+    // Generate changelog.md for releases
+    if !docs.releases.is_empty() {
+        let changelog_path = output_dir.join("changelog.md");
+        let changelog_content = generate_changelog_doc(docs)?;
+        out.write(&changelog_path, &changelog_content)?;
+    }
+
+    // Generate config.md for configuration options
+    if !docs.config_options.is_empty() {
+        let config_path = output_dir.join("config.md");
+        let config_content = generate_config_doc(docs, &links)?;
+        out.write(&config_path, &config_content)?;
     }
 
     Ok(())
 }
 
 /// Generate index page for the documentation
-pub async fn generate_index(output_dir: &Path, config: &DocgenConfig) -> Result<()> {
-    let index_path = output_dir.join("api").join("index.md");
+pub async fn generate_index(
+    out: &mut Output,
+    config: &DocgenConfig,
+    docs: &[ExtractedDocs],
+    models: &[ModelComparison],
+    conformance: &[Conformance],
+    graph: &DependencyGraph,
+    templates: &Templates,
+) -> Result<()> {
+    let index_path = out.dir().join("api").join("index.md");
 
-    if let Some(parent) = index_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
+    // Group packages by kind
+    let groups: Vec<_> = [
+        (PackageKind::Core, "Core Packages"),
+        (PackageKind::Adapter, "Database Adapters"),
+        (PackageKind::Frontend, "Frontend SDKs"),
+        (PackageKind::Mobile, "Mobile SDKs"),
+    ]
+    .into_iter()
+    .filter_map(|(kind, title)| {
+        let packages: Vec<_> = config
+            .packages
+            .iter()
+            .filter(|p| p.kind == kind)
+            .map(|p| serde_json::json!({ "name": p.name, "link": package_dir(&p.name) }))
+            .collect();
+        (!packages.is_empty()).then(|| serde_json::json!({ "title": title, "packages": packages }))
+    })
+    .collect();
+
+    let dependency_graph = (!graph.edges.is_empty() || !graph.references.is_empty())
+        .then(|| dependency_graph(config, graph));
+
+    let mut context = Context::new();
+    context.insert("config", config);
+    context.insert("groups", &groups);
+    context.insert("docs", docs);
+    context.insert("models", models);
+    context.insert("conformance", conformance);
+    context.insert("graph", graph);
+    context.insert("dependency_graph", &dependency_graph);
+    let changelog = changelog_page(out.dir())
+        .filter(|_| docs.iter().any(|d| !d.releases.is_empty()))
+        .map(|page| {
+            if page.starts_with(out.dir().join("api")) {
+                "./changelog.md"
+            } else {
+                "../changelog.md"
+            }
+        });
+    context.insert("has_changelog", &changelog.is_some());
+    context.insert("changelog_url", &changelog);
+    context.insert(
+        "has_security",
+        &docs.iter().any(|d| !d.security_notes.is_empty()),
+    );
+
+    let content = templates.render("api_index.md", &context)?;
+    out.write(&index_path, &content)?;
+
+    Ok(())
+}
+
+/// Generate the merged changelog of every package, newest release first
+pub async fn generate_changelog_index(out: &mut Output, docs: &[ExtractedDocs]) -> Result<()> {
+    let changelog_path = out.dir().join("api").join("changelog.md");
 
     let mut content = String::new();
-    content.push_str("# API Reference\n\n");
-    content.push_str("Complete API documentation for the Apple Auth Kit packages.\n\n");
+    content.push_str("# Changelog\n\n");
+    content.push_str(
+        "Releases of every package, merged from their `CHANGELOG.md` files. \
+         Unreleased changes come first, then releases by date.\n\n",
+    );
+    content.push_str(&merged_releases(docs, "./"));
 
-    // Group packages by kind
-    let mut core_packages = Vec::new();
-    let mut adapters = Vec::new();
-    let mut frontend = Vec::new();
-    let mut mobile = Vec::new();
+    out.write(&changelog_path, &content)?;
 
-    for pkg in &config.packages {
-        match pkg.kind {
-            PackageKind::Core => core_packages.push(pkg),
-            PackageKind::Adapter => adapters.push(pkg),
-            PackageKind::Frontend => frontend.push(pkg),
-            PackageKind::Mobile => mobile.push(pkg),
-        }
+    Ok(())
+}
+
+/// The page listing every package's releases: the generated
+/// `api/changelog.md`, or the site's own `changelog.md` when it has a
+/// `docgen:begin changelog` region. `None` when the site's page has none.
+pub fn changelog_page(output_dir: &Path) -> Option<PathBuf> {
+    let site = output_dir.join("changelog.md");
+    match std::fs::read_to_string(&site) {
+        Ok(content) => content.contains("docgen:begin changelog").then_some(site),
+        Err(_) => Some(output_dir.join("api").join("changelog.md")),
+    }
+}
+
+/// Every package's releases, unreleased changes first and then by date,
+/// linking the package changelogs under `api_dir`
+pub fn merged_releases(docs: &[ExtractedDocs], api_dir: &str) -> String {
+    let mut content = String::new();
+    let mut releases: Vec<_> = docs
+        .iter()
+        .flat_map(|d| d.releases.iter().map(move |r| (&d.package, r)))
+        .collect();
+    if releases.is_empty() {
+        content.push_str("*No package has a `CHANGELOG.md` yet.*\n");
+        return content;
     }
+    // Unreleased, then newest first; undated releases last in file order
+    releases.sort_by_key(|(_, r)| {
+        (
+            r.version != "Unreleased",
+            r.date.is_none(),
+            std::cmp::Reverse(r.date.clone()),
+        )
+    });
 
-    if !core_packages.is_empty() {
-        content.push_str("## Core Packages\n\n");
-        for pkg in core_packages {
-            let link = pkg.name.replace("@acedergren/", "").replace('-', "_");
-            content.push_str(&format!("- [{}](./{}/)\n", pkg.name, link));
+    for (package, release) in releases {
+        let link = package_dir(&package.name);
+        content.push_str(&format!(
+            "## [{}]({}{}/changelog.md) {}",
+            package.name, api_dir, link, release.version
+        ));
+        if let Some(date) = &release.date {
+            content.push_str(&format!(" - {}", date));
         }
-        content.push('\n');
+        if release.yanked {
+            content.push_str(" [YANKED]");
+        }
+        content.push_str("\n\n");
+        write_release_sections(&mut content, release);
+    }
+    content
+}
+
+/// The declared dependencies between packages and the names they import
+/// from each other, with mermaid node ids, for the `dependency_graph` macro
+fn dependency_graph(config: &DocgenConfig, graph: &DependencyGraph) -> serde_json::Value {
+    let node = |name: &str| {
+        name.chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+    };
+
+    let packages: Vec<_> = config
+        .packages
+        .iter()
+        .map(|p| serde_json::json!({ "name": p.name, "node": node(&p.name) }))
+        .collect();
+    let dependencies: Vec<_> = graph
+        .edges
+        .iter()
+        .map(|e| serde_json::json!({ "from": node(&e.from), "to": node(&e.to), "kind": e.kind }))
+        .collect();
+    let references: Vec<_> = graph
+        .references
+        .iter()
+        .map(|r| {
+            serde_json::json!({ "from": node(&r.from), "to": node(&r.to), "symbols": r.symbols })
+        })
+        .collect();
+    let undeclared: Vec<_> = graph
+        .references
+        .iter()
+        .filter(|r| !graph.edges.iter().any(|e| e.from == r.from && e.to == r.to))
+        .map(|r| serde_json::json!({ "from": r.from, "to": r.to }))
+        .collect();
+
+    serde_json::json!({
+        "packages": packages,
+        "dependencies": dependencies,
+        "references": references,
+        "undeclared": undeclared,
+    })
+}
+
+/// Directory of a package's pages under `api/`
+fn package_dir(name: &str) -> String {
+    name.replace("@acedergren/", "").replace('-', "_")
+}
+
+/// Relative link from one package's pages to another's
+fn package_link(from: &str, to: &str) -> String {
+    let depth = package_dir(from).split('/').count();
+    format!("{}{}/", "../".repeat(depth), package_dir(to))
+}
+
+/// Generate the side-by-side Swift/TypeScript model comparison page
+pub async fn generate_models_doc(
+    out: &mut Output,
+    models: &[ModelComparison],
+    links: &SourceLinks,
+) -> Result<()> {
+    let models_path = out.dir().join("api").join("models.md");
+
+    let mut content = String::new();
+    content.push_str("# Model Comparison\n\n");
+    content.push_str(
+        "Swift `Codable` models next to the TypeScript interfaces they exchange JSON with. \
+         Keys are shown as they appear on the wire.\n\n",
+    );
+
+    content.push_str("| Swift model | TypeScript | Errors | Warnings |\n");
+    content.push_str("|-------------|------------|--------|----------|\n");
+    for model in models {
+        let count = |severity: IssueSeverity| {
+            model
+                .issues
+                .iter()
+                .filter(|i| i.severity == severity)
+                .count()
+        };
+        let typescript: Vec<_> = model
+            .typescript
+            .iter()
+            .map(|(pkg, export)| format!("`{}` ({})", export.name, pkg))
+            .collect();
+        content.push_str(&format!(
+            "| [`{}`](#{}) | {} | {} | {} |\n",
+            model.swift.name,
+            model.swift.name.to_lowercase(),
+            typescript.join(", "),
+            count(IssueSeverity::Error),
+            count(IssueSeverity::Warning)
+        ));
     }
+    content.push('\n');
+
+    for model in models {
+        write_model_comparison(&mut content, model, links);
+    }
+
+    out.write(&models_path, &content)?;
+
+    Ok(())
+}
+
+/// Generate the security controls index from the packages' source comments
+pub async fn generate_security_index(
+    out: &mut Output,
+    docs: &[ExtractedDocs],
+    links: &SourceLinks,
+) -> Result<()> {
+    let security_path = out.dir().join("api").join("security.md");
+
+    let mut content = String::new();
+    content.push_str("# Security Controls\n\n");
+    content.push_str(
+        "Protections claimed by `Security:` sections and `@security` tags in the source. \
+         Each control links to the symbol that implements it.\n\n",
+    );
 
-    if !adapters.is_empty() {
-        content.push_str("## Database Adapters\n\n");
-        for pkg in adapters {
-            let link = pkg.name.replace("@acedergren/", "").replace('-', "_");
-            content.push_str(&format!("- [{}](./{}/)\n", pkg.name, link));
+    for pkg in docs.iter().filter(|d| !d.security_notes.is_empty()) {
+        content.push_str(&format!("## {}\n\n", pkg.package.name));
+        content.push_str("| Control | Symbol | Source |\n");
+        content.push_str("|---------|--------|--------|\n");
+
+        for note in &pkg.security_notes {
+            let file = note
+                .source_file
+                .strip_prefix(&pkg.package.path)
+                .unwrap_or(&note.source_file);
+            for claim in &note.claims {
+                content.push_str(&format!(
+                    "| {} | `{}` | [`{}:{}`]({}) |\n",
+                    claim.replace('|', "\\|"),
+                    note.symbol,
+                    file.display(),
+                    note.line,
+                    links.url(&note.source_file, note.line)
+                ));
+            }
         }
         content.push('\n');
     }
 
-    if !frontend.is_empty() {
-        content.push_str("## Frontend SDKs\n\n");
-        for pkg in frontend {
-            let link = pkg.name.replace("@acedergren/", "").replace('-', "_");
-            content.push_str(&format!("- [{}](./{}/)\n", pkg.name, link));
+    out.write(&security_path, &content)?;
+
+    Ok(())
+}
+
+/// Generate the adapter conformance matrix page
+pub async fn generate_conformance_doc(
+    out: &mut Output,
+    reports: &[Conformance],
+    links: &SourceLinks,
+) -> Result<()> {
+    let conformance_path = out.dir().join("api").join("conformance.md");
+
+    let mut content = String::new();
+    content.push_str("# Adapter Conformance\n\n");
+    content.push_str(
+        "Methods of the core repository interfaces and whether each adapter implements them.\n\n",
+    );
+    content.push_str(
+        "✅ implemented · ➖ optional, not implemented · ❌ missing · ⚠️ signature mismatch\n\n",
+    );
+
+    for report in reports {
+        content.push_str(&format!("## {}\n\n", report.interface.name));
+
+        if let Some(desc) = &report.interface.description {
+            content.push_str(desc);
+            content.push_str("\n\n");
         }
+
+        content.push_str(&format!(
+            "*Defined in [`{}`]({}) ({})*\n\n",
+            report
+                .interface
+                .source_file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy(),
+            links.url(&report.interface.source_file, report.interface.line),
+            report.package
+        ));
+
+        let mut header = "| Method |".to_string();
+        let mut divider = "|--------|".to_string();
+        for (pkg, class) in &report.implementations {
+            let short = pkg.rsplit('-').next().unwrap_or(pkg);
+            header.push_str(&format!(" `{}` ({}) |", class.name, short));
+            divider.push_str("------|");
+        }
+        content.push_str(&header);
+        content.push('\n');
+        content.push_str(&divider);
         content.push('\n');
-    }
 
-    if !mobile.is_empty() {
-        content.push_str("## Mobile SDKs\n\n");
-        for pkg in mobile {
-            let link = pkg.name.replace("@acedergren/", "").replace('-', "_");
-            content.push_str(&format!("- [{}](./{}/)\n", pkg.name, link));
+        for row in &report.methods {
+            let optional = if row.method.optional {
+                " (optional)"
+            } else {
+                ""
+            };
+            content.push_str(&format!("| `{}`{} |", row.method.name, optional));
+            for status in &row.status {
+                let cell = match status {
+                    MethodStatus::Implemented => "✅",
+                    MethodStatus::Omitted => "➖",
+                    MethodStatus::Missing => "❌",
+                    MethodStatus::Mismatch => "⚠️",
+                };
+                content.push_str(&format!(" {} |", cell));
+            }
+            content.push('\n');
         }
         content.push('\n');
+
+        if !report.issues.is_empty() {
+            content.push_str("### Problems\n\n");
+            for issue in &report.issues {
+                content.push_str(&format!("- {}\n", issue.message));
+            }
+            content.push('\n');
+        }
     }
 
-    std::fs::write(&index_path, content)?;
-    info!("Generated API index at {}", index_path.display());
+    out.write(&conformance_path, &content)?;
 
     Ok(())
 }
 
-fn generate_package_index(docs: &ExtractedDocs) -> Result<String> {
-    let mut content = String::new();
+fn write_model_comparison(content: &mut String, model: &ModelComparison, links: &SourceLinks) {
+    content.push_str(&format!("## {}\n\n", model.swift.name));
 
-    content.push_str(&format!("# {}\n\n", docs.package.name));
+    if let Some(desc) = &model.swift.description {
+        content.push_str(desc);
+        content.push_str("\n\n");
+    }
+
+    content.push_str(&format!(
+        "*Swift: [`{}`]({}) in {} ({})*\n\n",
+        model
+            .swift
+            .source_file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy(),
+        links.url(&model.swift.source_file, model.swift.line),
+        model.swift_package,
+        model.swift.conformances.join(", ")
+    ));
+
+    let mut header = "| JSON key | Swift |".to_string();
+    let mut divider = "|----------|-------|".to_string();
+    for (pkg, export) in &model.typescript {
+        let short = pkg.rsplit('/').next().unwrap_or(pkg);
+        header.push_str(&format!(" `{}` ({}) |", export.name, short));
+        divider.push_str("------|");
+    }
+    content.push_str(&header);
+    content.push('\n');
+    content.push_str(&divider);
+    content.push('\n');
+
+    let cell = |property: Option<&Property>| {
+        property.map_or("—".to_string(), |p| {
+            let optional = if p.optional { " (optional)" } else { "" };
+            format!("`{}`{}", p.type_annotation.replace('|', "\\|"), optional)
+        })
+    };
+    for key in &model.keys {
+        content.push_str(&format!("| `{}` | {} |", key.key, cell(key.swift.as_ref())));
+        for member in &key.typescript {
+            content.push_str(&format!(" {} |", cell(member.as_ref())));
+        }
+        content.push('\n');
+    }
+    content.push('\n');
+
+    if model.issues.is_empty() {
+        content.push_str("No differences found.\n\n");
+        return;
+    }
 
-    if !docs.package.description.is_empty() {
-        content.push_str(&format!("{}\n\n", docs.package.description));
+    content.push_str("### Differences\n\n");
+    for issue in &model.issues {
+        let marker = match issue.severity {
+            IssueSeverity::Error => "**Error**",
+            IssueSeverity::Warning => "Warning",
+            IssueSeverity::Info => "Info",
+        };
+        content.push_str(&format!("- {}: {}\n", marker, issue.message));
     }
+    content.push('\n');
+}
+
+fn generate_package_index(
+    docs: &ExtractedDocs,
+    templates: &Templates,
+    links: &SourceLinks,
+) -> Result<String> {
+    let package_links = |names: &[String]| {
+        names
+            .iter()
+            .map(|n| serde_json::json!({ "name": n, "link": package_link(&docs.package.name, n) }))
+            .collect::<Vec<_>>()
+    };
+
+    let plugin_surface = (!docs.plugins.is_empty()).then(|| plugin_surface(docs, links));
+
+    // Skip the first README heading if it matches package name
+    let readme = docs
+        .readme
+        .as_ref()
+        .map(|readme| skip_duplicate_heading(readme, &docs.package.name));
 
-    content.push_str(&format!("**Version:** {}\n\n", docs.package.version));
+    // Drizzle tables, grouped by dialect
+    let schema: Vec<_> = [
+        ("pg", "PostgreSQL"),
+        ("mysql", "MySQL"),
+        ("sqlite", "SQLite"),
+    ]
+    .into_iter()
+    .filter_map(|(dialect, name)| {
+        let prefix = format!("drizzle-{}:", dialect);
+        let tables: Vec<_> = docs
+            .tables
+            .iter()
+            .filter(|t| t.source.starts_with(&prefix))
+            .map(|t| {
+                serde_json::json!({
+                    "name": t.entity,
+                    "fields": t.fields,
+                    "file": t.file.strip_prefix(&docs.package.path).unwrap_or(&t.file),
+                    "source": links.url(&t.file, t.line),
+                })
+            })
+            .collect();
+        (!tables.is_empty()).then(|| serde_json::json!({ "name": name, "tables": tables }))
+    })
+    .collect();
 
-    // Installation
-    content.push_str("## Installation\n\n");
-    content.push_str("```bash\n");
-    content.push_str(&format!("npm install {}\n", docs.package.name));
-    content.push_str("# or\n");
-    content.push_str(&format!("pnpm add {}\n", docs.package.name));
-    content.push_str("```\n\n");
+    let mut context = Context::new();
+    context.insert("docs", docs);
+    context.insert("schema", &schema);
+    context.insert("depends_on", &package_links(&docs.package.internal_deps));
+    context.insert("used_by", &package_links(&docs.package.dependents));
+    context.insert("plugin_surface", &plugin_surface);
+    context.insert("readme", &readme);
+    context.insert("repo_url", &links.repo_url());
+    context.insert("package_dir", &links.repo_path(&docs.package.path));
+    context.insert("migrations", &docs.package.path.join("migrations").is_dir());
 
-    // Quick summary of exports
-    let interfaces: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Interface)
+    templates.render("package_index.md", &context)
+}
+
+/// Decorators, request properties, lifecycle hooks and route prefixes the
+/// package's Fastify plugins add, for the `plugin_surface` macro
+fn plugin_surface(docs: &ExtractedDocs, links: &SourceLinks) -> serde_json::Value {
+    let plugins: Vec<_> = docs
+        .plugins
+        .iter()
+        .map(|plugin| {
+            let file = plugin
+                .source_file
+                .strip_prefix(&docs.package.path)
+                .unwrap_or(&plugin.source_file);
+            serde_json::json!({
+                "title": plugin.name.clone().unwrap_or_else(|| file.display().to_string()),
+                "file": file.display().to_string(),
+                "source": links.url(&plugin.source_file, plugin.line),
+                "plugin": plugin,
+            })
+        })
         .collect();
-    let types: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Type)
+
+    // Request properties typed through `declare module 'fastify'`
+    let mut seen = Vec::new();
+    let request_properties: Vec<_> = docs
+        .request_decorations
+        .iter()
+        .filter(|p| {
+            let first = !seen.contains(&p.name);
+            seen.push(p.name.clone());
+            first
+        })
         .collect();
-    let functions: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Function)
+
+    // Prefixes the routes are registered under, and prefix options
+    let mut prefixes: Vec<&str> = Vec::new();
+    for route in &docs.routes {
+        if let Some(prefix) = route.prefix.as_deref().filter(|p| !prefixes.contains(p)) {
+            prefixes.push(prefix);
+        }
+    }
+    let prefix_options: Vec<_> = docs
+        .config_options
+        .iter()
+        .filter(|o| {
+            o.path
+                .rsplit('.')
+                .next()
+                .is_some_and(|name| name.to_lowercase().ends_with("prefix"))
+        })
+        .map(|o| {
+            serde_json::json!({
+                "root": o.root,
+                "path": o.path,
+                "default": option_default(o),
+                "description": o.description,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "plugins": plugins,
+        "request_properties": request_properties,
+        "prefixes": prefixes,
+        "prefix_options": prefix_options,
+    })
+}
+
+/// An export with the slug of its page and of its anchor on the kind page
+#[derive(serde::Serialize)]
+struct SymbolPage<'a> {
+    name: &'a str,
+    kind: &'a ExportKind,
+    slug: String,
+    /// Anchor on the kind page, when it is not the slugified name
+    anchor: String,
+    /// Kind page listing the symbol, `types` or `functions`
+    page: &'static str,
+    /// First sentence of the description
+    summary: String,
+    /// Link to the declaration
+    source_url: String,
+    export: &'a Export,
+}
+
+/// Sections of the types page, in page order
+const TYPE_SECTIONS: [(ExportKind, &str); 4] = [
+    (ExportKind::Interface, "Interfaces"),
+    (ExportKind::Type, "Type Aliases"),
+    (ExportKind::Enum, "Enums"),
+    (ExportKind::Class, "Classes"),
+];
+
+/// Pages of the package's exports that a kind page lists, in export order.
+/// Constants and variables have no kind page and get no page either.
+///
+/// A symbol's slug is its name slugified the way mkdocs `toc` slugifies
+/// the `### \`Name\`` heading, told apart from the package's other pages
+/// with `toc`'s `_1`, `_2` suffixes numbered by source location. Its
+/// anchor is the one `toc` gives the heading on the kind page, where the
+/// suffixes count the headings above it on that page only.
+fn symbol_pages<'a>(docs: &'a ExtractedDocs, links: &SourceLinks) -> Vec<SymbolPage<'a>> {
+    let page = |kind: &ExportKind| match kind {
+        ExportKind::Function => Some("functions"),
+        ExportKind::Const | ExportKind::Variable => None,
+        _ => Some("types"),
+    };
+    let exports: Vec<&Export> = docs
+        .package
+        .exports
+        .iter()
+        .filter(|e| page(&e.kind).is_some())
         .collect();
-    let classes: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Class)
+
+    let mut by_location = exports.clone();
+    by_location
+        .sort_by(|a, b| (&a.name, &a.source_file, a.line).cmp(&(&b.name, &b.source_file, b.line)));
+    let mut files: Vec<String> = PACKAGE_PAGES.iter().map(|p| p.to_string()).collect();
+    let slugs: Vec<(&Export, String)> = by_location
+        .into_iter()
+        .map(|export| (export, unique_slug(&export.name, &mut files)))
         .collect();
 
-    content.push_str("## Exports\n\n");
-    content.push_str("| Category | Count |\n");
-    content.push_str("|----------|-------|\n");
-    if !interfaces.is_empty() {
-        content.push_str(&format!("| Interfaces | {} |\n", interfaces.len()));
-    }
-    if !types.is_empty() {
-        content.push_str(&format!("| Types | {} |\n", types.len()));
+    // The kind pages, heading by heading as `toc` numbers them
+    let mut types: Vec<String> = std::iter::once(format!("{} - Types", docs.package.name))
+        .chain(std::iter::once("Class Diagram".to_string()))
+        .chain(TYPE_SECTIONS.iter().map(|(_, title)| title.to_string()))
+        .map(|heading| slugify(&heading))
+        .collect();
+    let mut functions = vec![slugify(&format!("{} - Functions", docs.package.name))];
+    let mut anchors: Vec<(&Export, String)> = Vec::new();
+    for (kind, _) in &TYPE_SECTIONS {
+        for export in exports.iter().filter(|e| e.kind == *kind) {
+            anchors.push((export, unique_slug(&export.name, &mut types)));
+        }
     }
-    if !functions.is_empty() {
-        content.push_str(&format!("| Functions | {} |\n", functions.len()));
+    for export in exports.iter().filter(|e| e.kind == ExportKind::Function) {
+        anchors.push((export, unique_slug(&export.name, &mut functions)));
     }
-    if !classes.is_empty() {
-        content.push_str(&format!("| Classes | {} |\n", classes.len()));
+
+    let find = |slugs: &[(&Export, String)], export: &Export| {
+        slugs
+            .iter()
+            .find(|(e, _)| std::ptr::eq(*e, export))
+            .map(|(_, slug)| slug.clone())
+            .unwrap_or_default()
+    };
+    exports
+        .into_iter()
+        .map(|export| {
+            let anchor = find(&anchors, export);
+            SymbolPage {
+                name: &export.name,
+                kind: &export.kind,
+                slug: find(&slugs, export),
+                anchor: if anchor == slugify(&export.name) {
+                    String::new()
+                } else {
+                    anchor
+                },
+                page: page(&export.kind).unwrap_or_default(),
+                summary: summary(export.description.as_deref()),
+                source_url: links.url(&export.source_file, export.line),
+                export,
+            }
+        })
+        .collect()
+}
+
+/// `name` slugified, with `toc`'s numeric suffix if it is already `taken`
+fn unique_slug(name: &str, taken: &mut Vec<String>) -> String {
+    let base = slugify(name);
+    let mut slug = base.clone();
+    let mut n = 0;
+    while taken.contains(&slug) {
+        n += 1;
+        slug = format!("{}_{}", base, n);
     }
-    content.push('\n');
+    taken.push(slug.clone());
+    slug
+}
+
+/// First sentence of the first paragraph, for table cells
+fn summary(description: Option<&str>) -> String {
+    let paragraph = description
+        .unwrap_or_default()
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sentence = match paragraph.find(". ") {
+        Some(end) => &paragraph[..=end],
+        None => &paragraph,
+    };
+    escape_table_cell(sentence)
+}
+
+fn generate_types_doc(
+    docs: &ExtractedDocs,
+    symbols: &[SymbolPage],
+    per_symbol: bool,
+    class_diagram: Option<String>,
+    templates: &Templates,
+) -> Result<String> {
+    let mut context = Context::new();
+    context.insert("docs", docs);
+    context.insert("symbols", symbols);
+    context.insert("per_symbol", &per_symbol);
+    context.insert("class_diagram", &class_diagram);
+    templates.render("types.md", &context)
+}
+
+fn generate_functions_doc(
+    docs: &ExtractedDocs,
+    symbols: &[SymbolPage],
+    per_symbol: bool,
+    templates: &Templates,
+) -> Result<String> {
+    let mut context = Context::new();
+    context.insert("docs", docs);
+    context.insert("symbols", symbols);
+    context.insert("per_symbol", &per_symbol);
+    templates.render("functions.md", &context)
+}
+
+fn generate_symbol_doc(
+    docs: &ExtractedDocs,
+    symbol: &SymbolPage,
+    templates: &Templates,
+) -> Result<String> {
+    let mut context = Context::new();
+    context.insert("docs", docs);
+    context.insert("symbol", symbol);
+    templates.render("symbol.md", &context)
+}
+
+fn generate_endpoints_doc(docs: &ExtractedDocs, links: &SourceLinks) -> Result<String> {
+    let mut content = String::new();
 
-    // Links to other pages
-    content.push_str("## Documentation\n\n");
-    content.push_str("- [Types Reference](./types.md)\n");
-    if !functions.is_empty() {
-        content.push_str("- [Functions Reference](./functions.md)\n");
+    content.push_str(&format!("# {} - HTTP Endpoints\n\n", docs.package.name));
+    content.push_str("HTTP routes registered by this package.\n\n");
+
+    content.push_str("| Method | Path | Auth | Rate limit | Description |\n");
+    content.push_str("|--------|------|------|------------|-------------|\n");
+    for route in &docs.routes {
+        let heading = format!("{} {}", route.method, route.full_path());
+        let auth = if route.auth_required { "Required" } else { "-" };
+        let rate_limit = route.rate_limit.as_ref().map_or("-".to_string(), |r| {
+            format!("{} / {}", r.max, r.time_window)
+        });
+        content.push_str(&format!(
+            "| `{}` | [`{}`](#{}) | {} | {} | {} |\n",
+            route.method,
+            route.full_path(),
+            slugify(&heading),
+            auth,
+            rate_limit,
+            route.description.as_deref().unwrap_or("-")
+        ));
     }
     content.push('\n');
 
-    // Include README content if available
-    if let Some(readme) = &docs.readme {
-        content.push_str("---\n\n");
-        // Skip the first heading if it matches package name
-        let readme_content = skip_duplicate_heading(readme, &docs.package.name);
-        content.push_str(&readme_content);
+    for route in &docs.routes {
+        write_route(&mut content, route, &docs.schemas, links);
     }
 
     Ok(content)
 }
 
-fn generate_types_doc(docs: &ExtractedDocs) -> Result<String> {
-    let mut content = String::new();
+fn write_route(content: &mut String, route: &Route, schemas: &[ZodSchema], links: &SourceLinks) {
+    content.push_str(&format!("## {} {}\n\n", route.method, route.full_path()));
 
-    content.push_str(&format!("# {} - Types\n\n", docs.package.name));
+    if let Some(desc) = &route.description {
+        content.push_str(desc);
+        content.push_str("\n\n");
+    }
 
-    // Group exports by kind
-    let interfaces: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Interface)
-        .collect();
-    let types: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Type)
-        .collect();
-    let enums: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Enum)
-        .collect();
-    let classes: Vec<_> = docs.package.exports.iter()
-        .filter(|e| e.kind == ExportKind::Class)
-        .collect();
+    content.push_str(&format!(
+        "*Defined in [`{}`]({})*\n\n",
+        route
+            .source_file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy(),
+        links.url(&route.source_file, route.line)
+    ));
+
+    if let Some(prefix) = &route.prefix {
+        content.push_str(&format!(
+            "**Route:** `{}` (registered under the `{}` prefix)\n\n",
+            route.path, prefix
+        ));
+    }
+
+    let auth = if route.auth_required {
+        "Required (access token checked by `fastify.authenticate`)"
+    } else {
+        "None"
+    };
+    content.push_str(&format!("**Authentication:** {}\n\n", auth));
 
-    if !interfaces.is_empty() {
-        content.push_str("## Interfaces\n\n");
-        for export in interfaces {
-            write_export(&mut content, export);
+    if let Some(rate_limit) = &route.rate_limit {
+        content.push_str(&format!(
+            "**Rate limit:** {} requests per {}\n\n",
+            rate_limit.max, rate_limit.time_window
+        ));
+    }
+
+    if !route.cookies_read.is_empty() || !route.cookies_set.is_empty() {
+        let list = |names: &[String]| {
+            names
+                .iter()
+                .map(|n| format!("`{}`", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut parts = Vec::new();
+        if !route.cookies_read.is_empty() {
+            parts.push(format!("reads {}", list(&route.cookies_read)));
+        }
+        if !route.cookies_set.is_empty() {
+            parts.push(format!("sets {}", list(&route.cookies_set)));
         }
+        content.push_str(&format!("**Cookies:** {}\n\n", parts.join("; ")));
     }
 
-    if !types.is_empty() {
-        content.push_str("## Type Aliases\n\n");
-        for export in types {
-            write_export(&mut content, export);
+    let find_schema = |name: &Option<String>| {
+        name.as_ref()
+            .and_then(|n| schemas.iter().find(|s| &s.name == n))
+    };
+
+    if !route.path_params.is_empty() {
+        content.push_str("**Path parameters:**\n\n");
+        match find_schema(&route.params_schema) {
+            Some(schema) => write_zod_fields(content, schema),
+            None => {
+                for param in &route.path_params {
+                    content.push_str(&format!("- `{}`\n", param));
+                }
+                content.push('\n');
+            }
         }
     }
 
-    if !enums.is_empty() {
-        content.push_str("## Enums\n\n");
-        for export in enums {
-            write_export(&mut content, export);
+    if let Some(schema) = find_schema(&route.query_schema) {
+        content.push_str("**Query parameters:**\n\n");
+        write_zod_fields(content, schema);
+    }
+
+    match (find_schema(&route.body_schema), &route.body_type) {
+        (Some(schema), _) => {
+            content.push_str(&format!("**Request body** (`{}`):\n\n", schema.name));
+            write_zod_fields(content, schema);
+        }
+        (None, Some(body_type)) => {
+            content.push_str("**Request body:**\n\n");
+            content.push_str("```typescript\n");
+            content.push_str(body_type);
+            content.push_str("\n```\n\n");
         }
+        (None, None) => {}
     }
 
-    if !classes.is_empty() {
-        content.push_str("## Classes\n\n");
-        for export in classes {
-            write_export(&mut content, export);
+    if !route.responses.is_empty() {
+        content.push_str("**Responses:**\n\n");
+        content.push_str("| Status | Description | Body |\n");
+        content.push_str("|--------|-------------|------|\n");
+        for response in &route.responses {
+            let fields = if response.fields.is_empty() {
+                "-".to_string()
+            } else {
+                response
+                    .fields
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            content.push_str(&format!(
+                "| {} | {} | {} |\n",
+                response.status,
+                escape_table_cell(&response.description),
+                fields
+            ));
         }
+        content.push('\n');
     }
 
-    Ok(content)
+    content.push_str("---\n\n");
+}
+
+fn write_zod_fields(content: &mut String, schema: &ZodSchema) {
+    content.push_str("| Field | Type | Required | Constraints |\n");
+    content.push_str("|-------|------|----------|-------------|\n");
+    for field in &schema.fields {
+        let required = if field.optional { "No" } else { "Yes" };
+        let checks: Vec<_> = field
+            .checks
+            .iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("{}: `{}`", name, value),
+                None => name.clone(),
+            })
+            .collect();
+        let checks = if checks.is_empty() {
+            "-".to_string()
+        } else {
+            checks.join(", ")
+        };
+        content.push_str(&format!(
+            "| `{}` | `{}` | {} | {} |\n",
+            field.name,
+            field.type_name,
+            required,
+            escape_table_cell(&checks)
+        ));
+    }
+    if schema.strict {
+        content.push_str("\nUnknown fields are rejected.\n");
+    }
+    content.push('\n');
+}
+
+/// Heading anchor as mkdocs `toc` computes it, which drops non-ASCII
+fn slugify(heading: &str) -> String {
+    let cleaned: String = heading
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-' || c.is_whitespace())
+        .collect();
+    let mut slug = String::new();
+    for c in cleaned.trim().to_lowercase().chars() {
+        if c == '-' || c.is_whitespace() {
+            if !slug.ends_with('-') {
+                slug.push('-');
+            }
+        } else {
+            slug.push(c);
+        }
+    }
+    slug
+}
+
+fn escape_table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
 }
 
-fn generate_functions_doc(functions: &[&Export], package_name: &str) -> Result<String> {
+fn generate_config_doc(docs: &ExtractedDocs, links: &SourceLinks) -> Result<String> {
     let mut content = String::new();
 
-    content.push_str(&format!("# {} - Functions\n\n", package_name));
+    content.push_str(&format!("# {} - Configuration\n\n", docs.package.name));
+    content.push_str(
+        "Every option accepted by the package's configuration objects, with its effective default.\n\n",
+    );
+
+    let mut roots: Vec<&str> = Vec::new();
+    for option in &docs.config_options {
+        if !roots.contains(&option.root.as_str()) {
+            roots.push(&option.root);
+        }
+    }
+
+    for root in roots {
+        content.push_str(&format!("## `{}`\n\n", root));
+
+        if let Some(export) = docs
+            .package
+            .exports
+            .iter()
+            .find(|e| e.kind == ExportKind::Interface && e.name == root)
+        {
+            if let Some(desc) = &export.description {
+                content.push_str(desc);
+                content.push_str("\n\n");
+            }
+            content.push_str(&format!(
+                "*Defined in [`{}`]({})*\n\n",
+                export
+                    .source_file
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+                links.url(&export.source_file, export.line)
+            ));
+        }
 
-    for export in functions {
-        write_export(&mut content, export);
+        content.push_str("| Option | Type | Required | Default | Description |\n");
+        content.push_str("|--------|------|----------|---------|-------------|\n");
+        for option in docs.config_options.iter().filter(|o| o.root == root) {
+            let required = if option.required { "Yes" } else { "No" };
+            let default = option_default(option).map_or("-".to_string(), |d| d.replace('|', "\\|"));
+            content.push_str(&format!(
+                "| `{}` | `{}` | {} | {} | {} |\n",
+                option.path,
+                option.type_annotation.replace('|', "\\|"),
+                required,
+                default,
+                option.description.as_deref().unwrap_or("-")
+            ));
+        }
+        content.push('\n');
     }
 
     Ok(content)
 }
 
-fn write_export(content: &mut String, export: &Export) {
-    content.push_str(&format!("### `{}`\n\n", export.name));
+/// An option's default as inline code, or each disagreeing default with
+/// where it is set
+fn option_default(option: &ConfigOption) -> Option<String> {
+    if let Some(default) = &option.default {
+        return Some(format!("`{}`", default));
+    }
+    let disputed: Vec<_> = option
+        .disputed_defaults
+        .iter()
+        .map(|d| format!("`{}` ({})", d.value, d.source))
+        .collect();
+    (!disputed.is_empty()).then(|| disputed.join(" or "))
+}
+
+fn generate_environment_doc(docs: &ExtractedDocs, links: &SourceLinks) -> Result<String> {
+    let mut content = String::new();
 
-    if let Some(deprecated) = &export.deprecated {
-        content.push_str(&format!("> ⚠️ **Deprecated:** {}\n\n", deprecated));
+    content.push_str(&format!(
+        "# {} - Environment Variables\n\n",
+        docs.package.name
+    ));
+    content.push_str(
+        "Environment variables read by the package, its documentation examples and example apps. \
+         Variables without a fallback must be set.\n\n",
+    );
+
+    content.push_str("| Variable | Read via | Fallback | Used by |\n");
+    content.push_str("|----------|----------|----------|---------|\n");
+    for var in &docs.env_vars {
+        let access: Vec<_> = var.access.iter().map(|a| format!("`{}`", a)).collect();
+        let fallback = var
+            .default
+            .as_ref()
+            .map_or("-".to_string(), |d| format!("`{}`", d.replace('|', "\\|")));
+        let used_by = if var.usages.iter().all(|u| u.example) {
+            "examples only"
+        } else {
+            "package code"
+        };
+        content.push_str(&format!(
+            "| [`{}`](#{}) | {} | {} | {} |\n",
+            var.name,
+            var.name.to_lowercase(),
+            access.join(", "),
+            fallback,
+            used_by
+        ));
     }
+    content.push('\n');
 
-    if let Some(desc) = &export.description {
-        content.push_str(desc);
+    for var in &docs.env_vars {
+        content.push_str(&format!("## `{}`\n\n", var.name));
+        for usage in &var.usages {
+            let file = usage
+                .source_file
+                .strip_prefix(&docs.package.path)
+                .unwrap_or(&usage.source_file);
+            let example = if usage.example { " (example)" } else { "" };
+            content.push_str(&format!(
+                "- [`{}:{}`]({}){}",
+                file.display(),
+                usage.line,
+                links.url(&usage.source_file, usage.line),
+                example
+            ));
+            if let Some(comment) = &usage.comment {
+                content.push_str(&format!(" - {}", comment));
+            }
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+
+    Ok(content)
+}
+
+fn generate_changelog_doc(docs: &ExtractedDocs) -> Result<String> {
+    let mut content = String::new();
+
+    content.push_str(&format!("# {} - Changelog\n\n", docs.package.name));
+
+    for release in &docs.releases {
+        content.push_str(&format!("## {}", release.version));
+        if let Some(date) = &release.date {
+            content.push_str(&format!(" - {}", date));
+        }
+        if release.yanked {
+            content.push_str(" [YANKED]");
+        }
         content.push_str("\n\n");
+        write_release_sections(&mut content, release);
     }
 
-    if let Some(sig) = &export.signature {
-        content.push_str("```typescript\n");
-        content.push_str(sig);
-        content.push_str("\n```\n\n");
+    Ok(content)
+}
+
+fn write_release_sections(content: &mut String, release: &Release) {
+    for section in &release.sections {
+        content.push_str(&format!("### {}\n\n", section.heading));
+        for entry in &section.entries {
+            content.push_str(&format!("- {}\n", entry));
+        }
+        content.push('\n');
     }
+}
+
+fn generate_sveltekit_doc(docs: &ExtractedDocs, links: &SourceLinks) -> Result<String> {
+    let mut content = String::new();
 
-    // Source location
     content.push_str(&format!(
-        "*Defined in [`{}`]({}:{})*\n\n",
-        export.source_file.file_name().unwrap_or_default().to_string_lossy(),
-        export.source_file.display(),
-        export.line
+        "# {} - SvelteKit Integration\n\n",
+        docs.package.name
     ));
+    content.push_str(
+        "Hooks, filesystem routes and `App.Locals` fields the package contributes to a \
+         SvelteKit app.\n\n",
+    );
 
-    if !export.params.is_empty() {
-        content.push_str("**Parameters:**\n\n");
-        content.push_str("| Name | Type | Required | Description |\n");
-        content.push_str("|------|------|----------|-------------|\n");
-        for param in &export.params {
-            let required = if param.optional { "No" } else { "Yes" };
-            let desc = param.description.as_deref().unwrap_or("-");
+    if !docs.hooks.is_empty() {
+        content.push_str("## Hooks\n\n");
+        content.push_str("| Hook | Type | Sets `locals` | Description | Source |\n");
+        content.push_str("|------|------|---------------|-------------|--------|\n");
+        for hook in &docs.hooks {
+            let locals: Vec<_> = hook.locals_set.iter().map(|l| format!("`{}`", l)).collect();
             content.push_str(&format!(
-                "| `{}` | `{}` | {} | {} |\n",
-                param.name, param.type_annotation, required, desc
+                "| `{}` | {} | {} | {} | {} |\n",
+                hook.name,
+                hook.hook_type
+                    .as_ref()
+                    .map_or("-".to_string(), |t| format!("`{}`", t)),
+                if locals.is_empty() {
+                    "-".to_string()
+                } else {
+                    locals.join(", ")
+                },
+                hook.description.as_deref().unwrap_or("-"),
+                source_link(&docs.package.path, &hook.source_file, hook.line, links)
             ));
         }
         content.push('\n');
     }
 
-    if let Some(returns) = &export.returns {
-        content.push_str(&format!("**Returns:** `{}`\n\n", returns));
+    if !docs.locals.is_empty() {
+        content.push_str("## `App.Locals`\n\n");
+        content.push_str("| Field | Type | Set by | Description |\n");
+        content.push_str("|-------|------|--------|-------------|\n");
+        for field in &docs.locals {
+            let set_by: Vec<_> = docs
+                .hooks
+                .iter()
+                .filter(|h| h.locals_set.contains(&field.name))
+                .map(|h| format!("`{}`", h.name))
+                .collect();
+            content.push_str(&format!(
+                "| `{}{}` | `{}` | {} | {} |\n",
+                field.name,
+                if field.optional { "?" } else { "" },
+                field.type_annotation.replace('|', "\\|"),
+                if set_by.is_empty() {
+                    "-".to_string()
+                } else {
+                    set_by.join(", ")
+                },
+                field.description.as_deref().unwrap_or("-")
+            ));
+        }
+        content.push('\n');
     }
 
-    if !export.examples.is_empty() {
-        content.push_str("**Example:**\n\n");
-        for example in &export.examples {
-            content.push_str("```typescript\n");
-            content.push_str(example);
-            content.push_str("\n```\n\n");
+    if !docs.page_routes.is_empty() {
+        content.push_str("## Routes\n\n");
+        for route in &docs.page_routes {
+            content.push_str(&format!("### `{}` ({})\n\n", route.path, route.file_name));
+            content.push_str(&format!(
+                "Source: {}\n\n",
+                source_link(&docs.package.path, &route.source_file, 1, links)
+            ));
+            if !route.handlers.is_empty() {
+                content.push_str("| Export | Description |\n");
+                content.push_str("|--------|-------------|\n");
+                for handler in &route.handlers {
+                    content.push_str(&format!(
+                        "| `{}` | {} |\n",
+                        handler.name,
+                        handler.description.as_deref().unwrap_or("-")
+                    ));
+                }
+                content.push('\n');
+            }
+            if !route.actions.is_empty() {
+                content.push_str("**Form actions:**\n\n");
+                for action in &route.actions {
+                    let name = if action.name == "default" {
+                        "default".to_string()
+                    } else {
+                        format!("?/{}", action.name)
+                    };
+                    content.push_str(&format!("- `{}`", name));
+                    if let Some(desc) = &action.description {
+                        content.push_str(&format!(" - {}", desc));
+                    }
+                    content.push('\n');
+                }
+                content.push('\n');
+            }
+            if !route.locals_used.is_empty() {
+                let locals: Vec<_> = route
+                    .locals_used
+                    .iter()
+                    .map(|l| format!("`{}`", l))
+                    .collect();
+                content.push_str(&format!("Reads `locals`: {}\n\n", locals.join(", ")));
+            }
+        }
+    }
+
+    Ok(content)
+}
+
+/// `[file:line](url)` with the file relative to the package root
+fn source_link(pkg_path: &Path, source_file: &Path, line: usize, links: &SourceLinks) -> String {
+    let file = source_file.strip_prefix(pkg_path).unwrap_or(source_file);
+    format!(
+        "[`{}:{}`]({})",
+        file.display(),
+        line,
+        links.url(source_file, line)
+    )
+}
+
+fn generate_collections_doc(docs: &ExtractedDocs, links: &SourceLinks) -> Result<String> {
+    let mut content = String::new();
+
+    content.push_str(&format!("# {} - Collections\n\n", docs.package.name));
+    content.push_str(
+        "MongoDB collections created by this adapter, extracted from the Mongoose schemas.\n\n",
+    );
+
+    for model in &docs.models {
+        write_model(&mut content, model, links);
+    }
+
+    Ok(content)
+}
+
+fn write_model(content: &mut String, model: &MongooseModel, links: &SourceLinks) {
+    content.push_str(&format!("## `{}`\n\n", model.name));
+
+    if let Some(desc) = &model.description {
+        content.push_str(desc);
+        content.push_str("\n\n");
+    }
+
+    if let Some(collection) = &model.collection {
+        content.push_str(&format!("**Collection:** `{}`\n\n", collection));
+    }
+
+    content.push_str(&format!(
+        "*Defined in [`{}`]({}) as `{}`*\n\n",
+        model
+            .source_file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy(),
+        links.url(&model.source_file, model.line),
+        model.schema
+    ));
+
+    content.push_str("### Fields\n\n");
+    content.push_str("| Field | Type | Required | Default | Options |\n");
+    content.push_str("|-------|------|----------|---------|---------|\n");
+    for field in &model.fields {
+        let required = if field.required { "Yes" } else { "No" };
+        let default = field
+            .default
+            .as_ref()
+            .map_or("-".to_string(), |d| format!("`{}`", d));
+
+        let mut options = Vec::new();
+        if field.unique {
+            options.push("unique".to_string());
+        }
+        if field.index {
+            options.push("index".to_string());
+        }
+        if field.sparse {
+            options.push("sparse".to_string());
         }
+        if let Some(expires) = &field.expires {
+            options.push(format!("expires: `{}`", expires));
+        }
+        if let Some(reference) = &field.reference {
+            options.push(format!("ref: `{}`", reference));
+        }
+        if !field.enum_values.is_empty() {
+            let values: Vec<_> = field
+                .enum_values
+                .iter()
+                .map(|v| format!("`{}`", v))
+                .collect();
+            options.push(format!("enum: {}", values.join(", ")));
+        }
+        let options = if options.is_empty() {
+            "-".to_string()
+        } else {
+            options.join(", ")
+        };
+
+        content.push_str(&format!(
+            "| `{}` | `{}` | {} | {} | {} |\n",
+            field.path, field.type_name, required, default, options
+        ));
+    }
+    for timestamp in &model.timestamps {
+        content.push_str(&format!(
+            "| `{}` | `Date` | No | *managed* | timestamps |\n",
+            timestamp
+        ));
+    }
+    content.push('\n');
+
+    if !model.indexes.is_empty() {
+        content.push_str("### Indexes\n\n");
+        content.push_str("| Keys | Options |\n");
+        content.push_str("|------|---------|\n");
+        for index in &model.indexes {
+            let keys: Vec<_> = index
+                .fields
+                .iter()
+                .map(|(field, direction)| format!("`{}: {}`", field, direction))
+                .collect();
+
+            let mut options = Vec::new();
+            if index.unique {
+                options.push("unique".to_string());
+            }
+            if index.sparse {
+                options.push("sparse".to_string());
+            }
+            if let Some(ttl) = &index.expire_after_seconds {
+                options.push(format!("TTL (`expireAfterSeconds: {}`)", ttl));
+            }
+            let options = if options.is_empty() {
+                "-".to_string()
+            } else {
+                options.join(", ")
+            };
+
+            content.push_str(&format!("| {} | {} |\n", keys.join(", "), options));
+        }
+        content.push('\n');
     }
 
     content.push_str("---\n\n");
@@ -0,0 +1,190 @@
+//! Mermaid class diagrams
+//!
+//! A package's interfaces and classes are drawn with their members, the
+//! `extends` and `implements` relationships between them, and an
+//! association wherever a property's type names another of them. Types
+//! from other packages appear when something here extends or implements
+//! them, which is how the adapter classes show the core repository
+//! interfaces. mkdocs renders the fences through the `mermaid` custom
+//! fence configured in `mkdocs.yml`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::types::{Export, ExportKind, ExtractedDocs};
+
+/// A relationship drawn between two types
+struct Edge {
+    from: String,
+    to: String,
+    arrow: &'static str,
+    label: Option<String>,
+}
+
+/// The class diagram of a package's interfaces and classes, as a fenced
+/// block, keeping the `max_types` most connected. `None` when the package
+/// has no interfaces or classes.
+pub fn class_diagram(docs: &ExtractedDocs, max_types: usize) -> Option<String> {
+    // The first definition of each name, in a stable order
+    let mut types: BTreeMap<&str, &Export> = BTreeMap::new();
+    let mut exports: Vec<_> = docs
+        .package
+        .exports
+        .iter()
+        .filter(|e| matches!(e.kind, ExportKind::Interface | ExportKind::Class))
+        .collect();
+    exports
+        .sort_by(|a, b| (&a.name, &a.source_file, a.line).cmp(&(&b.name, &b.source_file, b.line)));
+    for export in exports {
+        types.entry(export.name.as_str()).or_insert(export);
+    }
+    if types.is_empty() {
+        return None;
+    }
+
+    let word_re = regex::Regex::new(r"\b[A-Z]\w*\b").expect("valid type name regex");
+    // Types from elsewhere, and whether they are known to be interfaces
+    let mut external: BTreeMap<&str, bool> = BTreeMap::new();
+    for export in types.values() {
+        let interface = export.kind == ExportKind::Interface;
+        for parent in export
+            .extends
+            .iter()
+            .filter(|p| !types.contains_key(p.as_str()))
+        {
+            *external.entry(parent).or_default() |= interface;
+        }
+        for parent in export
+            .implements
+            .iter()
+            .filter(|p| !types.contains_key(p.as_str()))
+        {
+            external.insert(parent, true);
+        }
+    }
+
+    let mut edges = Vec::new();
+    for (name, export) in &types {
+        for parent in &export.extends {
+            edges.push(Edge {
+                from: parent.clone(),
+                to: name.to_string(),
+                arrow: "<|--",
+                label: None,
+            });
+        }
+        for interface in &export.implements {
+            edges.push(Edge {
+                from: interface.clone(),
+                to: name.to_string(),
+                arrow: "<|..",
+                label: None,
+            });
+        }
+
+        // One association per pair, labelled with the first property
+        let mut associated = BTreeSet::new();
+        for property in export.properties.iter().filter(|p| !p.method) {
+            for word in word_re.find_iter(&property.type_annotation) {
+                let target = word.as_str();
+                let known = types.contains_key(target) || external.contains_key(target);
+                if target != *name && known && associated.insert(target) {
+                    edges.push(Edge {
+                        from: name.to_string(),
+                        to: target.to_string(),
+                        arrow: "-->",
+                        label: Some(property.name.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    // Keep the most connected types; ties go to this package's, then by name
+    let mut degree: BTreeMap<&str, usize> = types.keys().map(|name| (*name, 0)).collect();
+    for edge in &edges {
+        *degree.entry(&edge.from).or_default() += 1;
+        *degree.entry(&edge.to).or_default() += 1;
+    }
+    let mut ranked: Vec<_> = degree.iter().collect();
+    ranked.sort_by_key(|(name, degree)| {
+        (
+            std::cmp::Reverse(**degree),
+            !types.contains_key(**name),
+            **name,
+        )
+    });
+    let total = ranked.len();
+    let shown: BTreeSet<&str> = ranked
+        .iter()
+        .take(max_types.max(1))
+        .map(|(name, _)| **name)
+        .collect();
+
+    let mut content = String::from("```mermaid\nclassDiagram\n");
+    for name in &shown {
+        match types.get(name) {
+            Some(export) => write_class(&mut content, export),
+            None if external.get(name) == Some(&true) => content.push_str(&format!(
+                "    class {} {{\n        <<interface>>\n    }}\n",
+                name
+            )),
+            None => content.push_str(&format!("    class {}\n", name)),
+        }
+    }
+    for edge in &edges {
+        if !shown.contains(edge.from.as_str()) || !shown.contains(edge.to.as_str()) {
+            continue;
+        }
+        content.push_str(&format!("    {} {} {}", edge.from, edge.arrow, edge.to));
+        if let Some(label) = &edge.label {
+            content.push_str(&format!(" : {}", label));
+        }
+        content.push('\n');
+    }
+    content.push_str("```");
+
+    if shown.len() < total {
+        content.push_str(&format!(
+            "\n\n*Showing the {} most connected of {} types.*",
+            shown.len(),
+            total
+        ));
+    }
+    Some(content)
+}
+
+/// A `class` block with the type's members
+fn write_class(content: &mut String, export: &Export) {
+    if export.kind != ExportKind::Interface && export.properties.is_empty() {
+        content.push_str(&format!("    class {}\n", export.name));
+        return;
+    }
+    content.push_str(&format!("    class {} {{\n", export.name));
+    if export.kind == ExportKind::Interface {
+        content.push_str("        <<interface>>\n");
+    }
+    for property in &export.properties {
+        if property.method {
+            content.push_str(&format!("        +{}()\n", property.name));
+        } else {
+            match member_type(&property.type_annotation) {
+                Some(type_name) => {
+                    content.push_str(&format!("        +{}: {}\n", property.name, type_name))
+                }
+                None => content.push_str(&format!("        +{}\n", property.name)),
+            }
+        }
+    }
+    content.push_str("    }\n");
+}
+
+/// A type annotation mermaid can show as written, with generics in its
+/// `~T~` syntax. Unions, literals, object types and multiple type
+/// arguments are left out.
+fn member_type(annotation: &str) -> Option<String> {
+    let simple =
+        regex::Regex::new(r"^[\w.]+(?:<[\w.]+>)?(?:\[\])*$").expect("valid member type regex");
+    simple
+        .is_match(annotation.trim())
+        .then(|| annotation.trim().replace(['<', '>'], "~"))
+}
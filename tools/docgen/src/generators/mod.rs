@@ -1,3 +1,10 @@
 //! Documentation generators
 
+pub mod embed;
+pub mod links;
 pub mod markdown;
+pub mod mermaid;
+pub mod nav;
+pub mod openapi;
+pub mod output;
+pub mod templates;
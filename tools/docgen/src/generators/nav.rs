@@ -0,0 +1,208 @@
+//! mkdocs navigation for the generated pages
+//!
+//! The generated pages are listed in a "Generated API" subtree of the
+//! `nav` in `mkdocs.yml`, between `# docgen:begin generated-api` and
+//! `# docgen:end generated-api` comments. Only the lines between the
+//! markers are rewritten; the file is never parsed and re-serialized, so
+//! hand-written entries, comments and tags like `!!python/name:` are kept
+//! as they are.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::links::absolute;
+use super::output::Output;
+use crate::types::{DocgenConfig, PackageKind};
+
+const BEGIN: &str = "# docgen:begin generated-api";
+const END: &str = "# docgen:end generated-api";
+
+/// Package pages listed in the nav, in order, with their titles. Symbol
+/// pages are reached from the kind pages instead.
+const PACKAGE_PAGES: [(&str, &str); 9] = [
+    ("index.md", "Overview"),
+    ("types.md", "Types"),
+    ("functions.md", "Functions"),
+    ("endpoints.md", "HTTP Endpoints"),
+    ("sveltekit.md", "SvelteKit Integration"),
+    ("collections.md", "Collections"),
+    ("config.md", "Configuration"),
+    ("environment.md", "Environment Variables"),
+    ("changelog.md", "Changelog"),
+];
+
+/// Pages about every package, after the package groups, with their titles
+const INDEX_PAGES: [(&str, &str); 4] = [
+    ("changelog.md", "Changelog"),
+    ("security.md", "Security"),
+    ("conformance.md", "Adapter Conformance"),
+    ("models.md", "Cross-Language Models"),
+];
+
+/// Insert or refresh the generated subtree of the `mkdocs.yml` nav at
+/// `root`, listing the pages written to `out`
+pub fn update(root: &Path, config: &DocgenConfig, out: &mut Output) -> Result<()> {
+    let mkdocs_path = root.join("mkdocs.yml");
+    if !mkdocs_path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&mkdocs_path)
+        .with_context(|| format!("Failed to read {}", mkdocs_path.display()))?;
+
+    // Nav entries are relative to the docs directory
+    let docs_dir = regex::Regex::new(r"(?m)^docs_dir:\s*['\x22]?([^'\x22\s#]+)")?
+        .captures(&content)
+        .map_or("docs".to_string(), |c| c[1].to_string());
+    if absolute(&root.join(&docs_dir)) != absolute(out.dir()) {
+        info!(
+            "Output is not the mkdocs docs_dir ({}), leaving the nav as is",
+            docs_dir
+        );
+        return Ok(());
+    }
+
+    let Some(updated) = splice(&content, |indent| {
+        entries(out.dir(), config, out.pages(), indent)
+    }) else {
+        info!("mkdocs.yml has no nav, leaving it to list every page");
+        return Ok(());
+    };
+
+    out.update(&mkdocs_path, &updated)
+}
+
+/// `content` with the lines between the markers replaced by `block`, or
+/// the marked block appended to the nav. `None` without a block-style nav.
+fn splice(content: &str, block: impl Fn(usize) -> Vec<String>) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let nav = lines.iter().position(|l| l.trim_end() == "nav:")?;
+
+    // The nav ends at the next top-level key
+    let end = lines[nav + 1..]
+        .iter()
+        .position(|l| !l.is_empty() && !l.starts_with([' ', '\t', '#', '-']))
+        .map_or(lines.len(), |i| nav + 1 + i);
+    let indent = lines[nav + 1..end]
+        .iter()
+        .find(|l| l.trim_start().starts_with("- "))
+        .map_or(2, |l| l.len() - l.trim_start().len());
+    let marker = |text: &str| format!("{}{}", " ".repeat(indent), text);
+
+    let begin = lines[nav + 1..end]
+        .iter()
+        .position(|l| l.trim() == BEGIN)
+        .map(|i| nav + 1 + i);
+    let close = begin.and_then(|b| {
+        lines[b + 1..end]
+            .iter()
+            .position(|l| l.trim() == END)
+            .map(|i| b + 1 + i)
+    });
+
+    let (before, after) = match (begin, close) {
+        (Some(b), Some(c)) => (&lines[..b], &lines[c + 1..]),
+        _ => {
+            // Append after the last entry, before any trailing blank lines
+            let last = lines[nav + 1..end]
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .map_or(nav + 1, |i| nav + 2 + i);
+            (&lines[..last], &lines[last..])
+        }
+    };
+
+    let mut out: Vec<String> = before.iter().map(|l| l.to_string()).collect();
+    out.push(marker(BEGIN));
+    out.extend(block(indent));
+    out.push(marker(END));
+    out.extend(after.iter().map(|l| l.to_string()));
+
+    let mut updated = out.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+/// Nav lines of the generated subtree, starting at `indent`
+fn entries(
+    output_dir: &Path,
+    config: &DocgenConfig,
+    pages: &[PathBuf],
+    indent: usize,
+) -> Vec<String> {
+    let api_dir = output_dir.join("api");
+    let item = |depth: usize, title: &str, page: Option<&Path>| {
+        let path = page.map(|p| {
+            let relative = p.strip_prefix(output_dir).unwrap_or(p);
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        });
+        format!(
+            "{}- {}:{}",
+            " ".repeat(indent + depth * 4),
+            quote(title),
+            path.map(|p| format!(" {}", p)).unwrap_or_default()
+        )
+    };
+    let written = |path: PathBuf| pages.contains(&path).then_some(path);
+
+    let mut lines = vec![item(0, "Generated API", None)];
+    if let Some(index) = written(api_dir.join("index.md")) {
+        lines.push(item(1, "Overview", Some(&index)));
+    }
+
+    let groups = [
+        (PackageKind::Core, "Core Packages"),
+        (PackageKind::Adapter, "Database Adapters"),
+        (PackageKind::Frontend, "Frontend SDKs"),
+        (PackageKind::Mobile, "Mobile SDKs"),
+    ];
+    for (kind, title) in groups {
+        let mut group = Vec::new();
+        for pkg in config.packages.iter().filter(|p| p.kind == kind) {
+            let dir = api_dir.join(pkg.name.replace("@acedergren/", "").replace('-', "_"));
+            let package_pages: Vec<_> = PACKAGE_PAGES
+                .iter()
+                .filter_map(|(file, title)| Some((*title, written(dir.join(file))?)))
+                .collect();
+            if package_pages.is_empty() {
+                continue;
+            }
+            group.push(item(2, &pkg.name, None));
+            for (title, page) in package_pages {
+                group.push(item(3, title, Some(&page)));
+            }
+        }
+        if !group.is_empty() {
+            lines.push(item(1, title, None));
+            lines.extend(group);
+        }
+    }
+
+    for (file, title) in INDEX_PAGES {
+        if let Some(page) = written(api_dir.join(file)) {
+            lines.push(item(1, title, Some(&page)));
+        }
+    }
+    lines
+}
+
+/// A nav title as a YAML key, quoted when it would not parse plain
+fn quote(title: &str) -> String {
+    let plain = !title.is_empty()
+        && !title.starts_with([
+            '@', '`', '!', '&', '*', '[', '{', '"', '\'', '%', '|', '>', '#', '-',
+        ])
+        && !title.contains(": ")
+        && !title.contains(" #");
+    if plain {
+        title.to_string()
+    } else {
+        format!("\"{}\"", title.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
@@ -0,0 +1,833 @@
+//! OpenAPI 3.1 document generator
+//!
+//! Combines extracted Fastify routes with the Zod request schemas and the
+//! TypeScript interfaces they return into a machine-readable API contract.
+//! Types are resolved on a best-effort basis: anything that cannot be traced
+//! back to a schema or interface is emitted as an unconstrained `{}`.
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::extractors::typescript::{object_entries, parse_members, split_top_level, unquote};
+use crate::types::{
+    Export, ExportKind, ExtractedDocs, Package, Property, Route, ZodField, ZodSchema,
+};
+
+/// Build the OpenAPI document for every route in `docs`.
+///
+/// When `package_filter` is set only matching packages contribute routes,
+/// but types are still resolved across the whole workspace.
+pub fn build_document(docs: &[ExtractedDocs], package_filter: Option<&str>) -> Value {
+    let mut builder = Builder::new(docs);
+
+    let api_docs: Vec<_> = docs
+        .iter()
+        .filter(|d| !d.routes.is_empty())
+        .filter(|d| package_filter.is_none_or(|f| d.package.name.contains(f)))
+        .collect();
+
+    // In registration order, as on the endpoints page
+    let mut paths: IndexMap<String, Map<String, Value>> = IndexMap::new();
+    for doc in &api_docs {
+        for route in &doc.routes {
+            let operation = builder.operation(route, &doc.package);
+            paths
+                .entry(openapi_path(&route.full_path()))
+                .or_default()
+                .insert(route.method.to_lowercase(), operation);
+        }
+    }
+
+    let info = match api_docs.as_slice() {
+        [doc] => json!({
+            "title": doc.package.name,
+            "version": doc.package.version,
+            "description": doc.package.description,
+        }),
+        _ => json!({
+            "title": "Apple Sign-In SDK HTTP API",
+            "version": api_docs.first().map_or("0.0.0", |d| d.package.version.as_str()),
+        }),
+    };
+
+    let routes: Vec<&Route> = api_docs.iter().flat_map(|d| d.routes.iter()).collect();
+
+    json!({
+        "openapi": "3.1.0",
+        "info": info,
+        "paths": paths,
+        "components": {
+            "schemas": builder.components,
+            "securitySchemes": security_schemes(&routes),
+        },
+    })
+}
+
+/// Resolves TypeScript types, Zod schemas and handler expressions into
+/// JSON Schema, collecting named types as components
+struct Builder<'a> {
+    interfaces: HashMap<&'a str, &'a Export>,
+    aliases: HashMap<&'a str, &'a Export>,
+    functions: HashMap<&'a str, &'a Export>,
+    schemas: HashMap<&'a str, &'a ZodSchema>,
+    request_decorations: Vec<&'a Property>,
+    /// In the order the routes first use them
+    components: IndexMap<String, Value>,
+    /// Package whose routes are being described; its own types win name lookups
+    package_path: PathBuf,
+}
+
+impl<'a> Builder<'a> {
+    fn new(docs: &'a [ExtractedDocs]) -> Self {
+        let mut builder = Builder {
+            interfaces: HashMap::new(),
+            aliases: HashMap::new(),
+            functions: HashMap::new(),
+            schemas: HashMap::new(),
+            request_decorations: Vec::new(),
+            components: IndexMap::new(),
+            package_path: PathBuf::new(),
+        };
+
+        // Sorted so that duplicate names resolve the same way on every run
+        let mut exports: Vec<&Export> = docs.iter().flat_map(|d| &d.package.exports).collect();
+        exports.sort_by(|a, b| (&a.source_file, a.line).cmp(&(&b.source_file, b.line)));
+
+        for export in exports {
+            let map = match export.kind {
+                ExportKind::Interface => &mut builder.interfaces,
+                ExportKind::Type => &mut builder.aliases,
+                ExportKind::Function => &mut builder.functions,
+                _ => continue,
+            };
+            map.entry(export.name.as_str()).or_insert(export);
+        }
+
+        for doc in docs {
+            for schema in &doc.schemas {
+                builder
+                    .schemas
+                    .entry(schema.name.as_str())
+                    .or_insert(schema);
+            }
+            builder.request_decorations.extend(&doc.request_decorations);
+        }
+
+        builder
+    }
+
+    /// Describe a single route as an OpenAPI operation object
+    fn operation(&mut self, route: &Route, package: &Package) -> Value {
+        self.package_path = package.path.clone();
+        let mut op = Map::new();
+
+        if let Some(description) = &route.description {
+            op.insert("summary".into(), json!(description));
+        }
+        op.insert("operationId".into(), json!(operation_id(route)));
+
+        let tag = route
+            .prefix
+            .as_deref()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty())
+            .unwrap_or(&package.name);
+        op.insert("tags".into(), json!([tag]));
+
+        let mut parameters = Vec::new();
+        let params_schema = route
+            .params_schema
+            .as_deref()
+            .and_then(|n| self.schemas.get(n).copied());
+        for param in &route.path_params {
+            let schema = params_schema
+                .and_then(|s| s.fields.iter().find(|f| &f.name == param))
+                .map_or_else(|| json!({ "type": "string" }), zod_field_schema);
+            parameters.push(json!({
+                "name": param,
+                "in": "path",
+                "required": true,
+                "schema": schema,
+            }));
+        }
+        if let Some(query) = route
+            .query_schema
+            .as_deref()
+            .and_then(|n| self.schemas.get(n).copied())
+        {
+            for field in &query.fields {
+                parameters.push(json!({
+                    "name": field.name,
+                    "in": "query",
+                    "required": !field.optional,
+                    "schema": zod_field_schema(field),
+                }));
+            }
+        }
+        if !parameters.is_empty() {
+            op.insert("parameters".into(), Value::Array(parameters));
+        }
+
+        let body = match route.body_schema.as_deref() {
+            Some(name) => match self.schemas.get(name).copied() {
+                Some(schema) => Some(self.zod_ref(schema)),
+                None => Some(json!({})),
+            },
+            None => route.body_type.as_deref().map(|t| self.ts_schema(t)),
+        };
+        if let Some(body) = body {
+            op.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": body } },
+                }),
+            );
+        }
+
+        let mut responses = Map::new();
+        for response in &route.responses {
+            let mut entry = Map::new();
+            let description = if response.description.is_empty() {
+                default_description(response.status)
+            } else {
+                response.description.clone()
+            };
+            entry.insert("description".into(), json!(description));
+
+            if response.status < 300 && !route.cookies_set.is_empty() {
+                let cookies = route
+                    .cookies_set
+                    .iter()
+                    .map(|c| format!("`{}`", c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                entry.insert(
+                    "headers".into(),
+                    json!({
+                        "Set-Cookie": {
+                            "description": format!("Sets {}", cookies),
+                            "schema": { "type": "string" },
+                        },
+                    }),
+                );
+            }
+
+            let schema = if !response.fields.is_empty() {
+                let mut properties = Map::new();
+                for (name, value) in &response.fields {
+                    properties.insert(name.clone(), self.expr_schema(value));
+                }
+                let required: Vec<_> = response.fields.iter().map(|(name, _)| name).collect();
+                Some(json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                }))
+            } else if response.status >= 400 {
+                Some(self.error_ref())
+            } else {
+                None
+            };
+            if let Some(schema) = schema {
+                entry.insert(
+                    "content".into(),
+                    json!({ "application/json": { "schema": schema } }),
+                );
+            }
+
+            responses.insert(response.status.to_string(), Value::Object(entry));
+        }
+        if responses.is_empty() {
+            responses.insert("200".into(), json!({ "description": "Success" }));
+        }
+        op.insert("responses".into(), Value::Object(responses));
+
+        if route.auth_required {
+            op.insert(
+                "security".into(),
+                json!([{ "bearerAuth": [] }, { "accessTokenCookie": [] }]),
+            );
+        } else if let Some(refresh) = route.cookies_read.iter().find(|c| c.contains("refresh")) {
+            // Routes that only read the cookie (logout) also work without it
+            let security = if route.cookies_set.contains(refresh) {
+                json!([{ "refreshTokenCookie": [] }])
+            } else {
+                json!([{ "refreshTokenCookie": [] }, {}])
+            };
+            op.insert("security".into(), security);
+        }
+
+        if let Some(rate_limit) = &route.rate_limit {
+            op.insert(
+                "x-rate-limit".into(),
+                json!({
+                    "max": rate_limit.max.parse::<u64>().map_or(json!(rate_limit.max), |m| json!(m)),
+                    "timeWindow": rate_limit.time_window,
+                }),
+            );
+        }
+
+        Value::Object(op)
+    }
+
+    /// `$ref` to a component generated from a Zod object schema
+    fn zod_ref(&mut self, schema: &ZodSchema) -> Value {
+        let name = self.zod_component_name(schema);
+        if !self.components.contains_key(&name) {
+            let mut properties = Map::new();
+            for field in &schema.fields {
+                properties.insert(field.name.clone(), zod_field_schema(field));
+            }
+            let required: Vec<_> = schema
+                .fields
+                .iter()
+                .filter(|f| !f.optional)
+                .map(|f| &f.name)
+                .collect();
+
+            let mut component = json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+            if let Some(description) = &schema.description {
+                component["description"] = json!(description);
+            }
+            if schema.strict {
+                component["additionalProperties"] = json!(false);
+            }
+            self.components.insert(name.clone(), component);
+        }
+        component_ref(&name)
+    }
+
+    /// Prefer the `z.infer<typeof schema>` alias name, else `fooSchema` -> `Foo`
+    fn zod_component_name(&self, schema: &ZodSchema) -> String {
+        let infer = format!("typeof {}>", schema.name);
+        let mut aliases: Vec<_> = self
+            .aliases
+            .values()
+            .filter(|a| {
+                a.signature
+                    .as_deref()
+                    .is_some_and(|s| s.contains("z.infer<") && s.contains(&infer))
+            })
+            .map(|a| a.name.clone())
+            .collect();
+        aliases.sort();
+
+        aliases.into_iter().next().unwrap_or_else(|| {
+            pascal_case(schema.name.strip_suffix("Schema").unwrap_or(&schema.name))
+        })
+    }
+
+    /// `$ref` to the error body sent by `@fastify/sensible` reply helpers
+    fn error_ref(&mut self) -> Value {
+        self.components
+            .entry("Error".to_string())
+            .or_insert_with(|| {
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "statusCode": { "type": "integer" },
+                        "error": { "type": "string" },
+                        "message": { "type": "string" },
+                    },
+                    "required": ["statusCode", "error", "message"],
+                })
+            });
+        component_ref("Error")
+    }
+
+    /// `$ref` to a component generated from an exported interface
+    fn interface_ref(&mut self, name: &str) -> Value {
+        if !self.components.contains_key(name) {
+            let export = self.interfaces[name];
+            // Placeholder first so self-referencing interfaces terminate
+            self.components.insert(name.to_string(), json!({}));
+            let mut component = self.object_schema(&export.properties);
+            if let Some(description) = &export.description {
+                component["description"] = json!(description);
+            }
+            self.components.insert(name.to_string(), component);
+        }
+        component_ref(name)
+    }
+
+    fn object_schema(&mut self, members: &[Property]) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for member in members.iter().filter(|m| !m.method) {
+            let mut schema = self.ts_schema(&member.type_annotation);
+            if let (Some(description), Some(obj)) = (&member.description, schema.as_object_mut()) {
+                if !obj.contains_key("$ref") {
+                    obj.insert("description".into(), json!(description));
+                }
+            }
+            properties.insert(member.name.clone(), schema);
+            if !member.optional {
+                required.push(member.name.clone());
+            }
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    /// JSON Schema for a TypeScript type annotation
+    fn ts_schema(&mut self, ts: &str) -> Value {
+        let ts = ts.trim();
+
+        let parts: Vec<String> = split_top_level(ts, '|')
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.len() > 1 {
+            let nullable = parts.iter().any(|p| p == "null" || p == "undefined");
+            let rest: Vec<_> = parts
+                .iter()
+                .filter(|p| *p != "null" && *p != "undefined")
+                .collect();
+
+            let schema = if rest.iter().all(|p| is_string_literal(p)) {
+                json!({
+                    "type": "string",
+                    "enum": rest.iter().map(|p| unquote(p)).collect::<Vec<_>>(),
+                })
+            } else if rest.len() == 1 {
+                self.ts_schema(rest[0])
+            } else {
+                json!({ "anyOf": rest.iter().map(|p| self.ts_schema(p)).collect::<Vec<_>>() })
+            };
+            return if nullable {
+                make_nullable(schema)
+            } else {
+                schema
+            };
+        }
+
+        if let Some(inner) = ts.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            return self.ts_schema(inner);
+        }
+        if let Some(inner) = ts.strip_suffix("[]") {
+            return json!({ "type": "array", "items": self.ts_schema(inner) });
+        }
+        if let Some(inner) = ts.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+            return self.object_schema(&parse_members(inner, 0));
+        }
+        if let Some(value) = js_literal(ts) {
+            return literal_schema(value);
+        }
+
+        if let Some((name, args)) = ts.strip_suffix('>').and_then(|t| t.split_once('<')) {
+            let args = split_top_level(args, ',');
+            return match (name.trim(), args.as_slice()) {
+                ("Array" | "ReadonlyArray", [item]) => {
+                    json!({ "type": "array", "items": self.ts_schema(item) })
+                }
+                ("Promise" | "Readonly" | "Partial" | "Required", [inner]) => self.ts_schema(inner),
+                ("Record", [_, value]) => json!({
+                    "type": "object",
+                    "additionalProperties": self.ts_schema(value),
+                }),
+                _ => json!({}),
+            };
+        }
+
+        match ts {
+            "string" => json!({ "type": "string" }),
+            "number" => json!({ "type": "number" }),
+            "boolean" => json!({ "type": "boolean" }),
+            "null" => json!({ "type": "null" }),
+            "Date" => json!({ "type": "string", "format": "date-time" }),
+            "object" => json!({ "type": "object" }),
+            _ if self.interfaces.contains_key(ts) => self.interface_ref(ts),
+            _ => self.alias_schema(ts),
+        }
+    }
+
+    /// Follow a type alias, including `z.infer<typeof schema>`
+    fn alias_schema(&mut self, name: &str) -> Value {
+        let Some(rhs) = self
+            .aliases
+            .get(name)
+            .and_then(|a| a.signature.as_deref())
+            .and_then(|s| s.split_once(" = "))
+            .map(|(_, rhs)| rhs.trim().to_string())
+        else {
+            return json!({});
+        };
+
+        if let Some(schema) = rhs
+            .strip_prefix("z.infer<typeof ")
+            .and_then(|r| r.strip_suffix('>'))
+            .and_then(|s| self.schemas.get(s.trim()).copied())
+        {
+            return self.zod_ref(schema);
+        }
+        // Guard against `type A = B` chains that loop back
+        if rhs == name {
+            return json!({});
+        }
+        self.ts_schema(&rhs)
+    }
+
+    /// Best-effort JSON Schema for a value expression in a response body
+    fn expr_schema(&mut self, expr: &str) -> Value {
+        let expr = expr.trim();
+        let expr = expr.strip_prefix("await ").unwrap_or(expr).trim();
+        let expr = expr.trim_end_matches('!');
+
+        if let Some((_, ts)) = expr.rsplit_once(" as ") {
+            return self.ts_schema(ts);
+        }
+        if let Some(value) = js_literal(expr) {
+            return literal_schema(value);
+        }
+        if expr.starts_with('`') {
+            return json!({ "type": "string" });
+        }
+        if let Some(inner) = expr.strip_prefix('{').and_then(|e| e.strip_suffix('}')) {
+            let entries = object_entries(inner);
+            let mut properties = Map::new();
+            for (key, value) in &entries {
+                properties.insert(key.clone(), self.expr_schema(value));
+            }
+            let required: Vec<_> = entries.iter().map(|(key, _)| key).collect();
+            return json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+        }
+
+        // Function call: use the declared return type of an exported function
+        if let Some(callee) = expr.find('(').map(|i| expr[..i].trim()) {
+            let name = callee.split('<').next().unwrap_or(callee);
+            let returns = self.functions.get(name).and_then(|f| f.returns.clone());
+            return match returns {
+                Some(returns) => self.ts_schema(&returns),
+                None => json!({}),
+            };
+        }
+
+        // `request.user` from the FastifyRequest augmentation
+        if let Some(member) = expr.strip_prefix("request.") {
+            let decoration = self
+                .request_decorations
+                .iter()
+                .find(|p| p.name == member)
+                .map(|p| p.type_annotation.clone());
+            return match decoration {
+                Some(ts) => self.ts_schema(&ts),
+                None => json!({}),
+            };
+        }
+
+        // `user.email`: look the member up on the interface named after the receiver
+        if let Some((receiver, member)) = expr.split_once('.') {
+            let receiver = pascal_case(receiver.trim_end_matches(['!', '?']));
+            let package_path = self.package_path.as_path();
+            let mut candidates: Vec<&Export> = self
+                .interfaces
+                .values()
+                .filter(|export| export.name.ends_with(&receiver))
+                .copied()
+                .collect();
+            candidates.sort_by_key(|export| {
+                (
+                    !is_within(&export.source_file, package_path),
+                    export.name.len(),
+                    export.name.as_str(),
+                )
+            });
+
+            let member_type = candidates.first().and_then(|export| {
+                export
+                    .properties
+                    .iter()
+                    .find(|p| p.name == member && !p.method)
+                    .map(|p| p.type_annotation.clone())
+            });
+            if let Some(ts) = member_type {
+                return self.ts_schema(&ts);
+            }
+        }
+
+        json!({})
+    }
+}
+
+/// JSON Schema for a single Zod object property
+fn zod_field_schema(field: &ZodField) -> Value {
+    let mut schema = match field.type_name.as_str() {
+        "string" => json!({ "type": "string" }),
+        "number" => json!({ "type": "number" }),
+        "bigint" => json!({ "type": "integer" }),
+        "boolean" => json!({ "type": "boolean" }),
+        "date" => json!({ "type": "string", "format": "date-time" }),
+        "array" => json!({ "type": "array" }),
+        "object" => json!({ "type": "object" }),
+        "enum" => json!({ "type": "string" }),
+        _ => json!({}),
+    };
+    let kind = field.type_name.as_str();
+    let mut nullable = false;
+
+    for (check, value) in &field.checks {
+        let number = value.as_deref().and_then(js_number);
+        match (check.as_str(), kind) {
+            ("enum", _) => {
+                if let Some(list) = value
+                    .as_deref()
+                    .and_then(|v| v.trim().strip_prefix('['))
+                    .and_then(|v| v.strip_suffix(']'))
+                {
+                    let values: Vec<_> = split_top_level(list, ',')
+                        .iter()
+                        .map(|v| unquote(v).to_string())
+                        .filter(|v| !v.is_empty())
+                        .collect();
+                    schema["enum"] = json!(values);
+                }
+            }
+            ("min", "string") => set(&mut schema, "minLength", number),
+            ("max", "string") => set(&mut schema, "maxLength", number),
+            ("length", "string") => {
+                set(&mut schema, "minLength", number.clone());
+                set(&mut schema, "maxLength", number);
+            }
+            ("min", "array") => set(&mut schema, "minItems", number),
+            ("max", "array") => set(&mut schema, "maxItems", number),
+            ("length", "array") => {
+                set(&mut schema, "minItems", number.clone());
+                set(&mut schema, "maxItems", number);
+            }
+            ("min", _) => set(&mut schema, "minimum", number),
+            ("max", _) => set(&mut schema, "maximum", number),
+            ("regex", _) => {
+                if let Some(pattern) = value.as_deref().and_then(regex_source) {
+                    schema["pattern"] = json!(pattern);
+                }
+            }
+            ("email", _) => schema["format"] = json!("email"),
+            ("uuid", _) => schema["format"] = json!("uuid"),
+            ("url", _) => schema["format"] = json!("uri"),
+            ("datetime", _) => schema["format"] = json!("date-time"),
+            ("int", _) => schema["type"] = json!("integer"),
+            ("positive", _) => schema["exclusiveMinimum"] = json!(0),
+            ("nonnegative", _) => schema["minimum"] = json!(0),
+            ("default", _) => {
+                if let Some(default) = value.as_deref().and_then(js_literal) {
+                    schema["default"] = default;
+                }
+            }
+            ("nullable", _) => nullable = true,
+            _ => {}
+        }
+    }
+
+    if nullable {
+        make_nullable(schema)
+    } else {
+        schema
+    }
+}
+
+/// Security schemes for the access token (header or cookie) and the
+/// refresh token cookie, named after the cookies the routes actually use
+fn security_schemes(routes: &[&Route]) -> Value {
+    let mut schemes = Map::new();
+
+    let cookies = || {
+        routes
+            .iter()
+            .flat_map(|r| r.cookies_set.iter().chain(&r.cookies_read))
+    };
+
+    if routes.iter().any(|r| r.auth_required) {
+        schemes.insert(
+            "bearerAuth".into(),
+            json!({
+                "type": "http",
+                "scheme": "bearer",
+                "bearerFormat": "JWT",
+                "description": "Short-lived access token sent as `Authorization: Bearer <token>`.",
+            }),
+        );
+        let access = cookies()
+            .find(|c| c.contains("access"))
+            .cloned()
+            .unwrap_or_else(|| "access_token".to_string());
+        schemes.insert(
+            "accessTokenCookie".into(),
+            json!({
+                "type": "apiKey",
+                "in": "cookie",
+                "name": access,
+                "description": "The same access token, as set by the sign-in and refresh endpoints.",
+            }),
+        );
+    }
+
+    if let Some(refresh) = cookies().find(|c| c.contains("refresh")) {
+        let mut description = "Opaque refresh token, rotated on every use.".to_string();
+        // The refresh endpoint reads the cookie and sets its replacement
+        if let Some(route) = routes
+            .iter()
+            .find(|r| r.cookies_read.contains(refresh) && r.cookies_set.contains(refresh))
+        {
+            description = format!(
+                "Opaque refresh token. `{} {}` exchanges it for a new access token and rotates it.",
+                route.method,
+                route.full_path()
+            );
+        }
+        schemes.insert(
+            "refreshTokenCookie".into(),
+            json!({
+                "type": "apiKey",
+                "in": "cookie",
+                "name": refresh,
+                "description": description,
+            }),
+        );
+    }
+
+    Value::Object(schemes)
+}
+
+fn is_within(file: &Path, dir: &Path) -> bool {
+    !dir.as_os_str().is_empty() && file.starts_with(dir)
+}
+
+fn component_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Allow `null` in addition to the schema's own type
+fn make_nullable(mut schema: Value) -> Value {
+    if let Some(type_name) = schema
+        .get("type")
+        .and_then(|t| t.as_str())
+        .map(String::from)
+    {
+        schema["type"] = json!([type_name, "null"]);
+        if let Some(values) = schema.get_mut("enum").and_then(|e| e.as_array_mut()) {
+            values.push(Value::Null);
+        }
+        schema
+    } else {
+        json!({ "anyOf": [schema, { "type": "null" }] })
+    }
+}
+
+fn literal_schema(value: Value) -> Value {
+    let type_name = match &value {
+        Value::String(_) => "string",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        _ => "null",
+    };
+    if value.is_null() {
+        json!({ "type": "null" })
+    } else {
+        json!({ "type": type_name, "const": value })
+    }
+}
+
+/// Parse a JavaScript literal (string, number, boolean, null)
+fn js_literal(s: &str) -> Option<Value> {
+    let s = s.trim();
+    match s {
+        "true" => Some(json!(true)),
+        "false" => Some(json!(false)),
+        "null" => Some(Value::Null),
+        _ if is_string_literal(s) && !s.starts_with('`') => Some(json!(unquote(s))),
+        _ => js_number(s),
+    }
+}
+
+fn js_number(s: &str) -> Option<Value> {
+    let s = s.trim().replace('_', "");
+    if let Ok(n) = s.parse::<i64>() {
+        return Some(json!(n));
+    }
+    s.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .map(|n| json!(n))
+}
+
+fn is_string_literal(s: &str) -> bool {
+    let s = s.trim();
+    s.len() >= 2
+        && ['\'', '"', '`']
+            .iter()
+            .any(|q| s.starts_with(*q) && s.ends_with(*q))
+}
+
+/// `/^[a-f0-9]{32}$/i` -> `^[a-f0-9]{32}$`
+fn regex_source(literal: &str) -> Option<String> {
+    let literal = literal.trim().strip_prefix('/')?;
+    let end = literal.rfind('/')?;
+    Some(literal[..end].to_string())
+}
+
+fn set(schema: &mut Value, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        schema[key] = value;
+    }
+}
+
+fn default_description(status: u16) -> String {
+    match status {
+        200..=299 => "Success",
+        400 => "Bad request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not found",
+        429 => "Too many requests",
+        _ => "Error",
+    }
+    .to_string()
+}
+
+/// `/auth/sessions/:id` -> `/auth/sessions/{id}`
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(param) => format!("{{{}}}", param.trim_end_matches('?')),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// `DELETE /auth/sessions/:id` -> `deleteAuthSessionsId`
+fn operation_id(route: &Route) -> String {
+    let mut id = route.method.to_lowercase();
+    for word in route
+        .full_path()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        id.push_str(&pascal_case(word));
+    }
+    id
+}
+
+fn pascal_case(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,266 @@
+//! Where the generated pages go
+//!
+//! Every page is handed to [`Output`] instead of being written directly.
+//! It only touches files whose content changed, so a run over up-to-date
+//! docs leaves the tree as it was, and in check mode it writes nothing and
+//! records the pages that differ from the files on disk.
+//!
+//! The pages docgen owns are listed with their content hashes in
+//! `.docgen-manifest.json` in the output directory. A page that an earlier
+//! run generated but this one did not is removed, and an existing file
+//! that is not in the manifest, or was edited since it was generated, is
+//! left alone unless `force` is set. Files written by another command,
+//! such as the OpenAPI document, share the manifest and are only pruned
+//! by that command.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// File listing the generated pages, in the output directory
+const MANIFEST: &str = ".docgen-manifest.json";
+
+/// The pages a run generated, by path relative to the output directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// SHA-256 of each page's content, in hex
+    files: BTreeMap<String, String>,
+
+    /// Command that wrote each file, for those `generate` did not write
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    commands: BTreeMap<String, String>,
+}
+
+/// Command whose files carry no entry in [`Manifest::commands`]
+const GENERATE: &str = "generate";
+
+/// Sink for the files of one `generate` run
+pub struct Output {
+    /// Output directory the pages are written under
+    dir: PathBuf,
+
+    /// Compare with the files on disk instead of writing
+    check: bool,
+
+    /// Overwrite and remove files docgen does not own
+    force: bool,
+
+    /// Command the files of this run belong to
+    command: &'static str,
+
+    /// Pages generated this run
+    pages: Vec<PathBuf>,
+
+    /// Files whose content differs from what is on disk
+    stale: Vec<PathBuf>,
+
+    /// Pages recorded by the previous run
+    previous: BTreeMap<String, String>,
+
+    /// Pages owned after this run
+    files: BTreeMap<String, String>,
+
+    /// Commands other than `generate` that wrote the pages
+    commands: BTreeMap<String, String>,
+
+    /// Existing files that were not overwritten because docgen does not
+    /// own them or they were edited
+    conflicts: Vec<PathBuf>,
+}
+
+impl Output {
+    /// Write under `dir`, or with `check` only compare with it
+    pub fn new(dir: &Path, check: bool, force: bool) -> Result<Self> {
+        let manifest_path = dir.join(MANIFEST);
+        let previous = match std::fs::read_to_string(&manifest_path) {
+            Ok(content) => serde_json::from_str::<Manifest>(&content)
+                .with_context(|| format!("Failed to parse {}", manifest_path.display()))?,
+            Err(_) => Manifest::default(),
+        };
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            check,
+            force,
+            command: GENERATE,
+            pages: Vec::new(),
+            stale: Vec::new(),
+            previous: previous.files,
+            files: BTreeMap::new(),
+            commands: previous.commands,
+            conflicts: Vec::new(),
+        })
+    }
+
+    /// Record the files as written by `command` rather than `generate`
+    pub fn written_by(self, command: &'static str) -> Self {
+        Self { command, ..self }
+    }
+
+    /// Output directory the pages are written under
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Pages generated this run, in the order they were written
+    pub fn pages(&self) -> &[PathBuf] {
+        &self.pages
+    }
+
+    /// Files that were out of date: rewritten or removed, or in check mode
+    /// left as is
+    pub fn stale(&self) -> &[PathBuf] {
+        &self.stale
+    }
+
+    /// Write a generated page, unless a file docgen does not own or a
+    /// hand-edited one is there
+    pub fn write(&mut self, path: &Path, content: &str) -> Result<()> {
+        self.pages.push(path.to_path_buf());
+        let key = self.key(path);
+        let current = std::fs::read_to_string(path).ok();
+
+        if let Some(current) = current.as_deref().filter(|c| *c != content) {
+            let reason = match self.previous.get(&key) {
+                None => Some("docgen did not generate"),
+                Some(hash) if *hash != digest(current) => {
+                    Some("was edited since docgen generated it")
+                }
+                Some(_) => None,
+            };
+            if let Some(reason) = reason.filter(|_| !self.force && !self.check) {
+                warn!("Not overwriting {}, which {}", path.display(), reason);
+                self.conflicts.push(path.to_path_buf());
+                // Still owned, so a later run does not take it for
+                // someone else's file
+                if let Some(hash) = self.previous.get(&key) {
+                    self.files.insert(key, hash.clone());
+                }
+                return Ok(());
+            }
+        }
+
+        if self.command == GENERATE {
+            self.commands.remove(&key);
+        } else {
+            self.commands.insert(key.clone(), self.command.to_string());
+        }
+        self.files.insert(key, digest(content));
+        self.update(path, content)
+    }
+
+    /// Write a file docgen only updates in part, such as a hand-written
+    /// page with generated regions or `mkdocs.yml`
+    pub fn update(&mut self, path: &Path, content: &str) -> Result<()> {
+        if std::fs::read_to_string(path).is_ok_and(|current| current == content) {
+            debug!("Unchanged {}", path.display());
+            return Ok(());
+        }
+        self.stale.push(path.to_path_buf());
+        if self.check {
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        info!("Wrote {}", path.display());
+        Ok(())
+    }
+
+    /// Record the pages of this run in the manifest. With `prune`, pages
+    /// an earlier run of the same command generated but this one did not
+    /// are removed; otherwise, as for a run over some of the packages,
+    /// they are kept.
+    ///
+    /// Fails when files docgen does not own were in the way.
+    pub fn finish(&mut self, prune: bool) -> Result<()> {
+        let previous = std::mem::take(&mut self.previous);
+        for (key, hash) in previous {
+            if self.files.contains_key(&key) {
+                continue;
+            }
+            let path = self.dir.join(&key);
+            let command = self.commands.get(&key).map_or(GENERATE, String::as_str);
+            if !prune || command != self.command {
+                self.files.insert(key, hash);
+                continue;
+            }
+            let Ok(current) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if digest(&current) != hash && !self.force {
+                warn!(
+                    "Keeping {}, which was edited since docgen generated it",
+                    path.display()
+                );
+                self.files.insert(key, hash);
+                continue;
+            }
+
+            self.commands.remove(&key);
+            self.stale.push(path.clone());
+            if !self.check {
+                self.remove(&path)?;
+            }
+        }
+
+        let manifest = Manifest {
+            files: std::mem::take(&mut self.files),
+            commands: std::mem::take(&mut self.commands),
+        };
+        let content = serde_json::to_string_pretty(&manifest)? + "\n";
+        self.update(&self.dir.join(MANIFEST), &content)?;
+
+        if !self.conflicts.is_empty() {
+            let paths: Vec<_> = self
+                .conflicts
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            bail!(
+                "Refusing to overwrite files docgen did not generate or that were edited (use --force to replace them): {}",
+                paths.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Remove a page that is no longer generated, and the directories it
+    /// leaves empty
+    fn remove(&self, path: &Path) -> Result<()> {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        info!("Removed {}", path.display());
+
+        let mut dir = path.parent();
+        while let Some(parent) = dir.filter(|d| d.starts_with(&self.dir) && *d != self.dir) {
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+        Ok(())
+    }
+
+    /// A page's manifest entry: its path under the output directory
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.dir)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// SHA-256 of a page, in hex
+fn digest(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
@@ -0,0 +1,85 @@
+//! Tera templates for the generated pages
+//!
+//! The package index, types, functions, symbol and API index pages, and the
+//! regions embedded in hand-written pages, are rendered from the built-in
+//! templates in `tools/docgen/templates`. A file with the same name in the
+//! configured `templates` directory replaces the built-in one, and may
+//! `{% import "macros.md" as macros %}` like they do.
+
+use anyhow::{Context as _, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use tera::{Context, Tera, Value};
+use tracing::info;
+
+use crate::types::Flavor;
+
+/// Built-in templates, by name
+const BUILTIN: [(&str, &str); 7] = [
+    ("macros.md", include_str!("../../templates/macros.md")),
+    (
+        "package_index.md",
+        include_str!("../../templates/package_index.md"),
+    ),
+    ("types.md", include_str!("../../templates/types.md")),
+    ("functions.md", include_str!("../../templates/functions.md")),
+    ("symbol.md", include_str!("../../templates/symbol.md")),
+    ("api_index.md", include_str!("../../templates/api_index.md")),
+    ("region.md", include_str!("../../templates/region.md")),
+];
+
+/// Page templates with project overrides applied
+pub struct Templates {
+    tera: Tera,
+
+    /// Passed to every template as `flavor`
+    flavor: Flavor,
+}
+
+impl Templates {
+    /// Load the built-in templates, replacing any that `dir` overrides
+    pub fn load(dir: Option<&Path>, flavor: Flavor) -> Result<Self> {
+        let mut sources: Vec<(String, String)> = Vec::new();
+        for (name, builtin) in BUILTIN {
+            let custom = dir.map(|d| d.join(name)).filter(|p| p.exists());
+            let source = match custom {
+                Some(path) => {
+                    info!("Using template {}", path.display());
+                    std::fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?
+                }
+                None => builtin.to_string(),
+            };
+            sources.push((name.to_string(), source));
+        }
+
+        let mut tera = Tera::default();
+        // Pages are markdown; escaping would mangle signatures and examples
+        tera.autoescape_on(Vec::new());
+        tera.register_filter("file_name", file_name);
+        tera.add_raw_templates(sources)
+            .context("Failed to parse templates")?;
+
+        Ok(Self { tera, flavor })
+    }
+
+    /// Render the template `name` with `context` and the flavor
+    pub fn render(&self, name: &str, context: &Context) -> Result<String> {
+        let mut context = context.clone();
+        context.insert("flavor", &self.flavor);
+        self.tera
+            .render(name, &context)
+            .with_context(|| format!("Failed to render template {}", name))
+    }
+}
+
+/// `{{ export.source_file | file_name }}`: the last component of a path
+fn file_name(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let path = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg("file_name expects a path"))?;
+    let name = Path::new(path)
+        .file_name()
+        .map_or(path.to_string(), |n| n.to_string_lossy().to_string());
+    Ok(Value::String(name))
+}
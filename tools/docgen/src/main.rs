@@ -13,7 +13,7 @@ mod extractors;
 mod generators;
 mod types;
 
-use commands::{generate, validate, watch};
+use commands::{check_models, check_schemas, generate, openapi, validate, watch};
 
 /// Documentation generator for Apple Sign-In SDK monorepo
 #[derive(Parser)]
@@ -49,6 +49,16 @@ enum Commands {
         /// Skip validation after generation
         #[arg(long)]
         no_validate: bool,
+
+        /// Fail if any generated page or region is out of date, without
+        /// writing anything
+        #[arg(long, conflicts_with = "package")]
+        check: bool,
+
+        /// Overwrite files docgen did not generate, and remove stale pages
+        /// even when they were edited
+        #[arg(long)]
+        force: bool,
     },
 
     /// Validate documentation against source code
@@ -58,6 +68,40 @@ enum Commands {
         strict: bool,
     },
 
+    /// Compare adapter storage schemas with the core types
+    CheckSchemas {
+        /// Strict mode - fail on warnings
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Compare Swift Codable models with the TypeScript interfaces
+    CheckModels {
+        /// Strict mode - fail on warnings
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Generate an OpenAPI 3.1 document for the HTTP routes
+    Openapi {
+        /// Output file (.json, or .yaml/.yml for YAML)
+        #[arg(short, long, default_value = "docs/api/openapi.json")]
+        output: String,
+
+        /// Only include routes from specific packages
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// Fail if the document is out of date, without writing it
+        #[arg(long)]
+        check: bool,
+
+        /// Overwrite the file even if docgen did not generate it or it was
+        /// edited
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Watch for changes and regenerate docs
     Watch {
         /// Output directory for generated docs
@@ -96,15 +140,31 @@ async fn main() -> Result<()> {
             output,
             package,
             no_validate,
+            check,
+            force,
         } => {
-            generate::run(&cli.root, &output, package.as_deref()).await?;
-            if !no_validate {
+            generate::run(&cli.root, &output, package.as_deref(), check, force).await?;
+            if !no_validate && !check {
                 validate::run(&cli.root, false).await?;
             }
         }
         Commands::Validate { strict } => {
             validate::run(&cli.root, strict).await?;
         }
+        Commands::CheckSchemas { strict } => {
+            check_schemas::run(&cli.root, strict).await?;
+        }
+        Commands::CheckModels { strict } => {
+            check_models::run(&cli.root, strict).await?;
+        }
+        Commands::Openapi {
+            output,
+            package,
+            check,
+            force,
+        } => {
+            openapi::run(&cli.root, &output, package.as_deref(), check, force).await?;
+        }
         Commands::Watch { output } => {
             watch::run(&cli.root, &output).await?;
         }
@@ -1,7 +1,7 @@
 //! Core types for documentation generation
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Represents a package in the monorepo
@@ -25,8 +25,59 @@ pub struct Package {
     /// Dependencies on other packages in monorepo
     pub internal_deps: Vec<String>,
 
+    /// Packages in the monorepo that depend on this one
+    pub dependents: Vec<String>,
+
     /// Exported symbols
     pub exports: Vec<Export>,
+
+    /// `peerDependencies` from package.json
+    pub peer_dependencies: Vec<PeerDependency>,
+
+    /// `Package.swift` of a Swift package
+    pub swift: Option<SwiftManifest>,
+
+    /// Repository to install the package from instead of the monorepo
+    pub repository: Option<String>,
+}
+
+/// A peer dependency the installing project provides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerDependency {
+    /// Package name
+    pub name: String,
+
+    /// Version range
+    pub version: String,
+
+    /// Marked optional in `peerDependenciesMeta`
+    pub optional: bool,
+}
+
+/// The parts of a `Package.swift` manifest needed to depend on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftManifest {
+    /// Package name
+    pub name: String,
+
+    /// `swift-tools-version` the manifest requires
+    pub tools_version: Option<String>,
+
+    /// Minimum platform versions (`.iOS(.v15)`)
+    pub platforms: Vec<SwiftPlatform>,
+
+    /// Library products other packages can depend on
+    pub products: Vec<String>,
+}
+
+/// A minimum deployment target from `platforms:`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftPlatform {
+    /// Platform name (iOS, macOS, ...)
+    pub name: String,
+
+    /// Minimum version (`15`, `10.15`)
+    pub version: String,
 }
 
 /// Kind of package
@@ -75,6 +126,18 @@ pub struct Export {
 
     /// Deprecation notice
     pub deprecated: Option<String>,
+
+    /// Release stage from an `@alpha`, `@beta` or `@experimental` tag
+    pub stability: Option<String>,
+
+    /// Members (for interfaces), or public methods (for classes)
+    pub properties: Vec<Property>,
+
+    /// Interfaces named in a class's `implements` clause
+    pub implements: Vec<String>,
+
+    /// Types named in an interface's or class's `extends` clause
+    pub extends: Vec<String>,
 }
 
 /// Kind of exported symbol
@@ -109,6 +172,34 @@ pub struct Parameter {
     pub default: Option<String>,
 }
 
+/// Interface member (property or method signature)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Property {
+    /// Member name
+    pub name: String,
+
+    /// TypeScript type, or the call signature for methods
+    pub type_annotation: String,
+
+    /// Description from the member's JSDoc
+    pub description: Option<String>,
+
+    /// Whether the member is optional (`name?:`)
+    pub optional: bool,
+
+    /// Whether the member is `readonly`
+    pub readonly: bool,
+
+    /// Whether the member is a method signature (`name(...): T`)
+    pub method: bool,
+
+    /// Default from a `@defaultValue`/`@default` tag
+    pub default: Option<String>,
+
+    /// Line number in source
+    pub line: usize,
+}
+
 /// Documentation validation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
@@ -156,14 +247,57 @@ pub enum IssueSeverity {
 /// Configuration for documentation generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocgenConfig {
-    /// Packages to document
+    /// Packages to document, discovered in the workspace if empty
+    #[serde(default)]
     pub packages: Vec<PackageConfig>,
 
     /// Output configuration
+    #[serde(default)]
     pub output: OutputConfig,
 
     /// Templates directory
     pub templates: Option<PathBuf>,
+
+    /// Repository URL for source links, `repo_url` in `mkdocs.yml` if unset
+    #[serde(default)]
+    pub repo_url: Option<String>,
+
+    /// Branch or tag source links point at instead of the checked out
+    /// commit, so the pages do not change with every commit
+    #[serde(default)]
+    pub source_ref: Option<String>,
+
+    /// Swift structs to compare with differently named TypeScript interfaces
+    #[serde(default)]
+    pub model_map: Vec<ModelMapping>,
+
+    /// Client endpoints known not to match the server routes, reported as
+    /// warnings until they are fixed
+    #[serde(default)]
+    pub known_drift: Vec<KnownDrift>,
+}
+
+/// A client endpoint whose mismatch with the server is tracked elsewhere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownDrift {
+    /// Client the call belongs to (e.g., "AppleSignInKit")
+    pub client: String,
+
+    /// Path as written in the client
+    pub path: String,
+
+    /// Why it is allowed, such as the issue tracking the fix
+    pub reason: String,
+}
+
+/// Pairs a Swift `Codable` struct with a TypeScript interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMapping {
+    /// Swift struct name
+    pub swift: String,
+
+    /// TypeScript interface name
+    pub typescript: String,
 }
 
 /// Configuration for a single package
@@ -183,6 +317,11 @@ pub struct PackageConfig {
 
     /// Files to exclude
     pub exclude: Vec<String>,
+
+    /// Repository or mirror with the package at its root, for package
+    /// managers that cannot install from a subdirectory, such as SwiftPM
+    #[serde(default)]
+    pub repository: Option<String>,
 }
 
 /// Output configuration
@@ -199,6 +338,69 @@ pub struct OutputConfig {
 
     /// Generate package readmes
     pub package_readme: bool,
+
+    /// Write one page per exported symbol, with the kind pages as indexes
+    #[serde(default)]
+    pub per_symbol: bool,
+
+    /// Markdown extensions the pages may use
+    #[serde(default)]
+    pub flavor: Flavor,
+
+    /// Mermaid class diagrams on the types pages
+    #[serde(default)]
+    pub diagrams: DiagramConfig,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("docs"),
+            api_reference: true,
+            changelog: true,
+            package_readme: true,
+            per_symbol: false,
+            flavor: Flavor::default(),
+            diagrams: DiagramConfig::default(),
+        }
+    }
+}
+
+/// Mermaid class diagram options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagramConfig {
+    /// Draw each package's interfaces and classes and how they relate
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Most types in one diagram; the least connected are left out
+    #[serde(default = "default_max_types")]
+    pub max_types: usize,
+}
+
+impl Default for DiagramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_types: default_max_types(),
+        }
+    }
+}
+
+fn default_max_types() -> usize {
+    20
+}
+
+/// Markdown dialect of the generated pages
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Flavor {
+    /// CommonMark, readable on GitHub and npm
+    #[default]
+    Plain,
+
+    /// mkdocs-material admonitions, content tabs and `attr_list` badges
+    Material,
 }
 
 /// Extracted documentation from source
@@ -208,11 +410,722 @@ pub struct ExtractedDocs {
     pub package: Package,
 
     /// All exports grouped by file
-    pub files: HashMap<PathBuf, Vec<Export>>,
+    pub files: BTreeMap<PathBuf, Vec<Export>>,
 
     /// README content if exists
     pub readme: Option<String>,
 
     /// CHANGELOG content if exists
     pub changelog: Option<String>,
+
+    /// Releases parsed from the CHANGELOG
+    pub releases: Vec<Release>,
+
+    /// Mongoose models defined by the package (MongoDB adapter)
+    pub models: Vec<MongooseModel>,
+
+    /// Drizzle tables defined by the package, one per dialect (Drizzle adapter)
+    pub tables: Vec<EntityModel>,
+
+    /// HTTP routes registered by the package
+    pub routes: Vec<Route>,
+
+    /// Zod validation schemas
+    pub schemas: Vec<ZodSchema>,
+
+    /// Properties added to `FastifyRequest` through module augmentation
+    pub request_decorations: Vec<Property>,
+
+    /// Decorators and lifecycle hooks added by the package's Fastify plugins
+    pub plugins: Vec<PluginSurface>,
+
+    /// Flattened options of the package's configuration interfaces
+    pub config_options: Vec<ConfigOption>,
+
+    /// Environment variables read by the package and its examples
+    pub env_vars: Vec<EnvVar>,
+
+    /// Security claims made in source comments
+    pub security_notes: Vec<SecurityNote>,
+
+    /// SvelteKit hooks (`Handle`, `HandleFetch`, ...) the package provides
+    pub hooks: Vec<SvelteKitHook>,
+
+    /// SvelteKit filesystem routes under `src/routes`
+    pub page_routes: Vec<SvelteKitRoute>,
+
+    /// Fields declared on `App.Locals`
+    pub locals: Vec<Property>,
+}
+
+/// A Mongoose model extracted from a `new Schema(...)` definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MongooseModel {
+    /// Model name passed to `connection.model()` (e.g., "User")
+    pub name: String,
+
+    /// Variable holding the schema (e.g., "userSchema")
+    pub schema: String,
+
+    /// Collection name from the schema options
+    pub collection: Option<String>,
+
+    /// JSDoc description of the schema
+    pub description: Option<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number of the schema definition
+    pub line: usize,
+
+    /// Field definitions, flattened to dotted paths
+    pub fields: Vec<SchemaField>,
+
+    /// Indexes declared with `schema.index()`
+    pub indexes: Vec<SchemaIndex>,
+
+    /// Timestamp fields managed by the `timestamps` option
+    pub timestamps: Vec<String>,
+}
+
+/// A single field of a Mongoose schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaField {
+    /// Dotted field path (e.g., "profile.name")
+    pub path: String,
+
+    /// Schema type (String, Number, Date, ObjectId, ...)
+    pub type_name: String,
+
+    /// Whether the field is required
+    pub required: bool,
+
+    /// Default value expression
+    pub default: Option<String>,
+
+    /// Single-field index
+    pub index: bool,
+
+    /// Unique constraint
+    pub unique: bool,
+
+    /// Sparse index
+    pub sparse: bool,
+
+    /// TTL from the `expires` option
+    pub expires: Option<String>,
+
+    /// Allowed values from the `enum` option
+    pub enum_values: Vec<String>,
+
+    /// Referenced model from the `ref` option
+    pub reference: Option<String>,
+}
+
+/// A schema-level index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaIndex {
+    /// Indexed fields with their direction (`1`, `-1`, `text`, ...)
+    pub fields: Vec<(String, String)>,
+
+    /// Unique constraint
+    pub unique: bool,
+
+    /// Sparse index
+    pub sparse: bool,
+
+    /// TTL in seconds from `expireAfterSeconds`
+    pub expire_after_seconds: Option<String>,
+}
+
+/// An HTTP route registered on a Fastify instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    /// HTTP method (GET, POST, ...)
+    pub method: String,
+
+    /// Path as registered (e.g., "/sessions/:id")
+    pub path: String,
+
+    /// Prefix the routes plugin is registered under (e.g., "/auth")
+    pub prefix: Option<String>,
+
+    /// Summary from the comment above the registration
+    pub description: Option<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number of the registration
+    pub line: usize,
+
+    /// Path parameters (`:id`)
+    pub path_params: Vec<String>,
+
+    /// Request body type from the route generic (`{ Body: ... }`)
+    pub body_type: Option<String>,
+
+    /// Schema validating `request.body`
+    pub body_schema: Option<String>,
+
+    /// Schema validating `request.params`
+    pub params_schema: Option<String>,
+
+    /// Schema validating `request.query`
+    pub query_schema: Option<String>,
+
+    /// Whether an authentication preHandler runs
+    pub auth_required: bool,
+
+    /// Rate limit from `config.rateLimit`
+    pub rate_limit: Option<RateLimit>,
+
+    /// Cookies the handler reads from the request
+    pub cookies_read: Vec<String>,
+
+    /// Cookies the handler sets on the response
+    pub cookies_set: Vec<String>,
+
+    /// Responses the handler can send
+    pub responses: Vec<RouteResponse>,
+}
+
+impl Route {
+    /// Path including the registration prefix
+    pub fn full_path(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}{}", prefix.trim_end_matches('/'), self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Rate limit applied to a route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Maximum requests per window
+    pub max: String,
+
+    /// Window length (e.g., "1 minute")
+    pub time_window: String,
+}
+
+/// A response a route handler can send
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteResponse {
+    /// HTTP status code
+    pub status: u16,
+
+    /// Messages or reason for this status
+    pub description: String,
+
+    /// Top-level keys of the JSON body and the expression producing each
+    /// (shorthand properties are expanded to the local's initializer)
+    pub fields: Vec<(String, String)>,
+}
+
+/// A Zod object schema used for request validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZodSchema {
+    /// Exported schema name (e.g., "appleCallbackSchema")
+    pub name: String,
+
+    /// JSDoc description
+    pub description: Option<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+
+    /// Whether unknown keys are rejected (`.strict()`)
+    pub strict: bool,
+
+    /// Object properties
+    pub fields: Vec<ZodField>,
+}
+
+/// A property of a [`ZodSchema`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZodField {
+    /// Property name
+    pub name: String,
+
+    /// Zod base type (string, number, boolean, enum, ...)
+    pub type_name: String,
+
+    /// Whether the property may be omitted (`.optional()`, `.default()`)
+    pub optional: bool,
+
+    /// Refinements in declaration order, e.g. `("max", Some("2048"))`
+    pub checks: Vec<(String, Option<String>)>,
+}
+
+/// Storage-agnostic view of an entity (SQL table, Drizzle table,
+/// Mongoose collection or TypeScript interface), used for drift checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityModel {
+    /// Table, collection or interface name as written (e.g., "AUTH_USERS")
+    pub entity: String,
+
+    /// Where the definition comes from (e.g., "oracle:AUTH_USERS")
+    pub source: String,
+
+    /// Source file path
+    pub file: PathBuf,
+
+    /// Line number of the definition
+    pub line: usize,
+
+    /// Fields with names normalized to camelCase
+    pub fields: Vec<EntityField>,
+}
+
+/// A field of an [`EntityModel`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityField {
+    /// camelCase field name
+    pub name: String,
+
+    /// Type as written in the source definition
+    pub type_name: String,
+
+    /// Whether the stored value may be null or absent
+    pub nullable: bool,
+
+    /// Line number of the field
+    pub line: usize,
+}
+
+/// A Swift `struct` declaration (used for Codable models)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftStruct {
+    /// Type name
+    pub name: String,
+
+    /// Adopted protocols (e.g., "Codable", "Sendable")
+    pub conformances: Vec<String>,
+
+    /// Description from `///` comments
+    pub description: Option<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+
+    /// Stored properties (`let`/`var`, excluding static and computed)
+    pub properties: Vec<Property>,
+
+    /// Explicit `CodingKeys` as `(property, key)`; empty when synthesized
+    pub coding_keys: Vec<(String, String)>,
+}
+
+/// An HTTP call hardcoded in a client SDK
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientEndpoint {
+    /// Client the call belongs to (e.g., "AppleSignInKit")
+    pub client: String,
+
+    /// Constant or function the path comes from, if named
+    pub name: Option<String>,
+
+    /// HTTP method, when the call site shows it
+    pub method: Option<String>,
+
+    /// Path as written, relative to the client's base URL
+    pub path: String,
+
+    /// JSON body keys as sent on the wire, when they can be determined
+    pub body_fields: Option<Vec<String>>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// A Swift `Codable` struct and the TypeScript interfaces describing the
+/// same JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelComparison {
+    /// Swift package the struct belongs to
+    pub swift_package: String,
+
+    /// The Swift side
+    pub swift: SwiftStruct,
+
+    /// Matching interfaces as `(package name, interface)`
+    pub typescript: Vec<(String, Export)>,
+
+    /// One row per JSON key seen on either side
+    pub keys: Vec<ModelKey>,
+
+    /// Drift found between the two sides
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// A JSON key of a [`ModelComparison`] and how each side declares it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelKey {
+    /// Key as it appears on the wire
+    pub key: String,
+
+    /// Swift property, with its Swift type
+    pub swift: Option<Property>,
+
+    /// TypeScript property per interface, in `typescript` order
+    pub typescript: Vec<Option<Property>>,
+}
+
+/// A core repository interface and the adapter classes implementing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conformance {
+    /// Package declaring the interface
+    pub package: String,
+
+    /// The required contract
+    pub interface: Export,
+
+    /// Implementing classes as `(package name, class)`
+    pub implementations: Vec<(String, Export)>,
+
+    /// One row per interface method
+    pub methods: Vec<ConformanceRow>,
+
+    /// Missing and mismatched methods
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// An interface method of a [`Conformance`] and how each class provides it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceRow {
+    /// The interface method
+    pub method: Property,
+
+    /// Status per class, in `implementations` order
+    pub status: Vec<MethodStatus>,
+}
+
+/// How a class provides an interface method
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MethodStatus {
+    /// Implemented with a compatible signature
+    Implemented,
+
+    /// Optional method the class leaves out
+    Omitted,
+
+    /// Required method the class leaves out
+    Missing,
+
+    /// Implemented with an incompatible signature
+    Mismatch,
+}
+
+/// One option path of a configuration interface, flattened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigOption {
+    /// Top-level interface the path starts from (e.g., "AuthConfig")
+    pub root: String,
+
+    /// Dotted option path (e.g., "jwt.accessTokenTtl")
+    pub path: String,
+
+    /// TypeScript type
+    pub type_annotation: String,
+
+    /// Whether the option and all its parents are required
+    pub required: bool,
+
+    /// Description from the member's JSDoc
+    pub description: Option<String>,
+
+    /// Effective default, from code or the JSDoc
+    pub default: Option<String>,
+
+    /// Defaults that disagree, when there is no single effective one
+    pub disputed_defaults: Vec<ConfigDefault>,
+
+    /// Source file declaring the member
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// One of the disagreeing defaults of a configuration option
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDefault {
+    /// Default value as written
+    pub value: String,
+
+    /// Where it is set: a source file name, or "JSDoc"
+    pub source: String,
+}
+
+/// An environment variable read somewhere in a package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvVar {
+    /// Variable name (e.g., "JWT_SECRET")
+    pub name: String,
+
+    /// How it is read (`process.env`, `import.meta.env`, `$env/static/private`, ...)
+    pub access: Vec<String>,
+
+    /// Fallback used when unset (`process.env.PORT || 3000`)
+    pub default: Option<String>,
+
+    /// Every place the variable is read
+    pub usages: Vec<EnvUsage>,
+}
+
+/// One read of an [`EnvVar`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvUsage {
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+
+    /// Whether the read is in a doc comment or an example app
+    pub example: bool,
+
+    /// Comment on the same line or directly above
+    pub comment: Option<String>,
+}
+
+/// Security claims a doc comment makes about one symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityNote {
+    /// Documented symbol, or the `@module` name for file headers
+    pub symbol: String,
+
+    /// Description preceding the security section
+    pub summary: Option<String>,
+
+    /// One entry per bullet or `@security` tag
+    pub claims: Vec<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// A code region marked with `// docgen:example <name>` ... `// docgen:end`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleRegion {
+    /// Region name used by `{@include name}`
+    pub name: String,
+
+    /// Code fence language (e.g., "typescript")
+    pub language: String,
+
+    /// Region body, dedented
+    pub code: String,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line of the opening marker
+    pub line: usize,
+}
+
+/// A SvelteKit hook, exported from a hooks file or built by a hook factory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SvelteKitHook {
+    /// Hook name (e.g., "handle")
+    pub name: String,
+
+    /// Hook type from `@sveltejs/kit` (e.g., "Handle"), if annotated
+    pub hook_type: Option<String>,
+
+    /// Description from JSDoc
+    pub description: Option<String>,
+
+    /// `event.locals` fields the hook assigns
+    pub locals_set: Vec<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// A SvelteKit route file (`+server.ts`, `+page.server.ts`, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SvelteKitRoute {
+    /// Route path derived from the directory (e.g., "/auth/[provider]")
+    pub path: String,
+
+    /// Route file name (e.g., "+page.server.ts")
+    pub file_name: String,
+
+    /// HTTP handlers and `load`, in source order
+    pub handlers: Vec<SvelteKitHandler>,
+
+    /// Form actions from `export const actions`
+    pub actions: Vec<SvelteKitHandler>,
+
+    /// `locals` fields the route reads
+    pub locals_used: Vec<String>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+}
+
+/// An exported handler or form action of a route file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SvelteKitHandler {
+    /// Export or action name (e.g., "GET", "load", "default")
+    pub name: String,
+
+    /// Description from JSDoc
+    pub description: Option<String>,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// What a Fastify plugin file adds to the instance it is registered on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginSurface {
+    /// Plugin name from the `fastify-plugin` metadata
+    pub name: Option<String>,
+
+    /// Plugins that must be registered first (`dependencies`)
+    pub dependencies: Vec<String>,
+
+    /// Supported Fastify version range (`fastify`)
+    pub fastify_version: Option<String>,
+
+    /// `decorate`, `decorateRequest` and `decorateReply` calls
+    pub decorators: Vec<Decorator>,
+
+    /// `addHook` registrations
+    pub hooks: Vec<LifecycleHook>,
+
+    /// Source file path
+    pub source_file: PathBuf,
+
+    /// Line number of the `fastify-plugin` call, or 1
+    pub line: usize,
+}
+
+/// A property a plugin decorates the instance, request or reply with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decorator {
+    /// "instance", "request" or "reply"
+    pub target: String,
+
+    /// Decorated property name
+    pub name: String,
+
+    /// Type from the matching `declare module 'fastify'` augmentation
+    pub type_annotation: Option<String>,
+
+    /// Description from the augmentation, or the comment above the call
+    pub description: Option<String>,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// A lifecycle hook registered with `addHook`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleHook {
+    /// Hook name (e.g., "onRequest", "preHandler")
+    pub name: String,
+
+    /// Handler, when it is a named function rather than inline
+    pub handler: Option<String>,
+
+    /// Description from the comment above the registration
+    pub description: Option<String>,
+
+    /// Line number in source
+    pub line: usize,
+}
+
+/// A dependency of one monorepo package on another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Dependent package name
+    pub from: String,
+
+    /// Dependency package name
+    pub to: String,
+
+    /// Field the dependency is declared in
+    pub kind: DependencyKind,
+}
+
+/// How a monorepo dependency is declared
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    /// `dependencies`, `optionalDependencies` or `Package.swift`
+    Runtime,
+    /// `peerDependencies`
+    Peer,
+    /// `devDependencies` with a `workspace:` specifier
+    Dev,
+}
+
+/// Declared dependencies and imports between the monorepo packages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    /// Dependencies declared in the package manifests
+    pub edges: Vec<DependencyEdge>,
+
+    /// Names imported across packages, declared or not
+    pub references: Vec<TypeReference>,
+}
+
+/// Names one package imports from another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeReference {
+    /// Importing package name
+    pub from: String,
+
+    /// Imported package name
+    pub to: String,
+
+    /// Imported names, sorted
+    pub symbols: Vec<String>,
+}
+
+/// A release entry from a Keep a Changelog style CHANGELOG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    /// Version without a leading "v", or "Unreleased"
+    pub version: String,
+
+    /// Release date (YYYY-MM-DD)
+    pub date: Option<String>,
+
+    /// Marked `[YANKED]`
+    pub yanked: bool,
+
+    /// Change sections in file order
+    pub sections: Vec<ReleaseSection>,
+}
+
+/// A `### Added`/`### Fixed`/... section of a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSection {
+    /// Section heading (e.g., "Added", "Minor Changes")
+    pub heading: String,
+
+    /// One entry per bullet
+    pub entries: Vec<String>,
 }